@@ -0,0 +1,94 @@
+// adapter/src/amm.rs
+use std::collections::HashMap;
+
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anyhow::Result;
+
+/// Snapshot of an on-chain account, as fetched by the aggregator.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Account {
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub owner: Pubkey,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyedAccount {
+    pub key: Pubkey,
+    pub account: Account,
+}
+
+pub type AccountMap = HashMap<Pubkey, Account>;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SwapMode {
+    #[default]
+    ExactIn,
+    ExactOut,
+}
+
+/// `amount` is the input for `ExactIn` and the output for `ExactOut`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct QuoteParams {
+    pub amount: u64,
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    pub swap_mode: SwapMode,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Quote {
+    pub in_amount: u64,
+    pub out_amount: u64,
+    pub fee_amount: u64,
+    pub fee_mint: Pubkey,
+    pub price_impact_bps: u64,
+}
+
+/// `in_amount` and `out_amount` are the slippage bounds of the swap: for
+/// `ExactIn` the input and minimum output, for `ExactOut` the maximum input and
+/// the output.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SwapParams {
+    pub source_mint: Pubkey,
+    pub destination_mint: Pubkey,
+    pub source_token_account: Pubkey,
+    pub destination_token_account: Pubkey,
+    pub token_transfer_authority: Pubkey,
+    pub in_amount: u64,
+    pub out_amount: u64,
+    pub swap_mode: SwapMode,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SwapAndAccountMetas {
+    pub data: Vec<u8>,
+    pub account_metas: Vec<AccountMeta>,
+}
+
+/// Quote and routing interface aggregators drive a pool through: build it from
+/// the pool account, keep it fresh with `update`, then quote offline and emit
+/// the swap instruction's accounts.
+pub trait Amm {
+    fn from_keyed_account(keyed_account: &KeyedAccount) -> Result<Self>
+    where
+        Self: Sized;
+
+    fn label(&self) -> String;
+
+    fn program_id(&self) -> Pubkey;
+
+    fn key(&self) -> Pubkey;
+
+    fn get_reserve_mints(&self) -> Vec<Pubkey>;
+
+    fn get_accounts_to_update(&self) -> Vec<Pubkey>;
+
+    fn update(&mut self, account_map: &AccountMap) -> Result<()>;
+
+    fn quote(&self, quote_params: &QuoteParams) -> Result<Quote>;
+
+    fn get_swap_and_account_metas(&self, swap_params: &SwapParams) -> Result<SwapAndAccountMetas>;
+
+    fn clone_amm(&self) -> Box<dyn Amm + Send + Sync>;
+}
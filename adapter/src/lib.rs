@@ -0,0 +1,9 @@
+// adapter/src/lib.rs
+//! Aggregator adapter for Prism launch pools: loads pool and vault snapshots,
+//! quotes offline with the program's own swap math and emits the `swap_wsol`
+//! account metas, or `swap_v2` ones for pools quoted in an SPL token.
+pub mod amm;
+pub mod prism;
+
+pub use amm::*;
+pub use prism::PrismAmm;
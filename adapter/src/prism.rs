@@ -0,0 +1,290 @@
+// adapter/src/prism.rs
+use anchor_lang::prelude::{AccountInfo, Clock, Pubkey, Rent, SolanaSysvar};
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::AccountDeserialize;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_hook::TransferHook;
+use anchor_spl::token_2022::spl_token_2022::extension::{BaseStateWithExtensions, StateWithExtensions};
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use anyhow::{anyhow, bail, ensure, Result};
+use prism_liquidity_pool_sdk::decode::{decode_global_config, decode_pool};
+use prism_liquidity_pool_sdk::instructions::{swap_v2, swap_wsol, SwapFeeRecipients};
+use prism_liquidity_pool_sdk::pda::{find_global_config, PoolAddresses};
+use prism_liquidity_pool_sdk::quote::{quote_detailed, MintTransferFee};
+use prism_liquidity_pool_sdk::{args, ID};
+use prism_liquidity_pool_sdk::state::{self, LiquidityPool, PoolStatus, SwapDirection};
+
+use crate::amm::*;
+
+/// Accounts loaded by `update`; the pool alone can be quoted once these are in.
+/// The raw mint is kept so Token-2022 transfer fees can be applied per epoch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct PoolSnapshot {
+    mint_data: Vec<u8>,
+    epoch: u64,
+    has_transfer_hook: bool,
+    token_decimals: u8,
+    is_paused: bool,
+    token_vault_balance: u64,
+    /// Quote reserve the vault can pay out: lamports above the SOL vault's
+    /// rent-exempt minimum, or the quote vault's token balance.
+    quote_vault_balance: u64,
+}
+
+/// Launch curve pool of one mint. The quote leg of a SOL pool is quoted under
+/// the wrapped SOL mint and settles in the swap authority's WSOL token
+/// account; other pools settle in the authority's account of their quote
+/// mint. Quotes and slippage bounds are in what the user sends and receives,
+/// net of any Token-2022 transfer fee.
+#[derive(Clone)]
+pub struct PrismAmm {
+    addresses: PoolAddresses,
+    pool: LiquidityPool,
+    global_config: Pubkey,
+    snapshot: Option<PoolSnapshot>,
+}
+
+impl PrismAmm {
+    pub fn pool(&self) -> &LiquidityPool {
+        &self.pool
+    }
+
+    fn direction(&self, input_mint: &Pubkey, output_mint: &Pubkey) -> Result<SwapDirection> {
+        let token_mint = self.pool.token_mint;
+        let quote_mint = self.pool.quote_mint;
+
+        if *input_mint == quote_mint && *output_mint == token_mint {
+            Ok(SwapDirection::Buy)
+        } else if *input_mint == token_mint && *output_mint == quote_mint {
+            Ok(SwapDirection::Sell)
+        } else {
+            bail!("pool {} does not trade {} for {}", self.addresses.pool, input_mint, output_mint)
+        }
+    }
+
+    fn snapshot(&self) -> Result<&PoolSnapshot> {
+        self.snapshot
+            .as_ref()
+            .ok_or_else(|| anyhow!("pool {} has not been updated", self.addresses.pool))
+    }
+}
+
+impl PoolSnapshot {
+    fn transfer_fee(&self) -> MintTransferFee<'_> {
+        MintTransferFee { mint_data: &self.mint_data, epoch: self.epoch }
+    }
+}
+
+fn program_mode(swap_mode: SwapMode, in_amount: u64, out_amount: u64) -> state::SwapMode {
+    match swap_mode {
+        SwapMode::ExactIn => state::SwapMode::ExactIn { amount_in: in_amount, min_amount_out: out_amount },
+        SwapMode::ExactOut => state::SwapMode::ExactOut { amount_out: out_amount, max_amount_in: in_amount },
+    }
+}
+
+fn get_account<'a>(account_map: &'a AccountMap, key: &Pubkey) -> Result<&'a Account> {
+    account_map.get(key).ok_or_else(|| anyhow!("missing account {}", key))
+}
+
+fn decode<T: AccountDeserialize>(account: &Account) -> Result<T> {
+    Ok(T::try_deserialize(&mut account.data.as_slice())?)
+}
+
+fn decode_clock(account: &Account) -> Result<Clock> {
+    let mut lamports = account.lamports;
+    let mut data = account.data.clone();
+    let account_info = AccountInfo::new(&sysvar::clock::ID, false, false, &mut lamports, &mut data, &account.owner, false, 0);
+
+    Ok(Clock::from_account_info(&account_info)?)
+}
+
+fn has_transfer_hook(mint: &Account) -> Result<bool> {
+    if mint.owner != spl_token_2022::ID {
+        return Ok(false);
+    }
+
+    let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint.data)?;
+    Ok(mint_state
+        .get_extension::<TransferHook>()
+        .map(|hook| Option::<Pubkey>::from(hook.program_id).is_some())
+        .unwrap_or(false))
+}
+
+impl Amm for PrismAmm {
+    fn from_keyed_account(keyed_account: &KeyedAccount) -> Result<Self> {
+        ensure!(keyed_account.account.owner == ID, "account {} is not owned by the pool program", keyed_account.key);
+
+        let pool = decode_pool(&keyed_account.account.data)?;
+        // Token programs are only known once the mints are loaded; `update`
+        // corrects them for Token-2022 mints.
+        let addresses = PoolAddresses::with_quote(
+            pool.token_mint,
+            anchor_spl::token::ID,
+            pool.quote_mint,
+            anchor_spl::token::ID,
+            pool.pool_index,
+        );
+        ensure!(addresses.pool == keyed_account.key, "account {} is not the pool PDA of its mints", keyed_account.key);
+
+        Ok(Self {
+            addresses,
+            pool,
+            global_config: find_global_config().0,
+            snapshot: None,
+        })
+    }
+
+    fn label(&self) -> String {
+        "Prism".to_string()
+    }
+
+    fn program_id(&self) -> Pubkey {
+        ID
+    }
+
+    fn key(&self) -> Pubkey {
+        self.addresses.pool
+    }
+
+    fn get_reserve_mints(&self) -> Vec<Pubkey> {
+        vec![self.pool.quote_mint, self.pool.token_mint]
+    }
+
+    fn get_accounts_to_update(&self) -> Vec<Pubkey> {
+        let mut accounts = vec![
+            self.addresses.pool,
+            self.addresses.token_mint,
+            self.addresses.pool_token_account,
+            self.global_config,
+            sysvar::clock::ID,
+        ];
+        if self.addresses.is_native_quote() {
+            accounts.push(self.addresses.sol_vault);
+        } else {
+            accounts.extend([self.addresses.quote_mint, self.addresses.quote_vault]);
+        }
+
+        accounts
+    }
+
+    fn update(&mut self, account_map: &AccountMap) -> Result<()> {
+        let pool = decode_pool(&get_account(account_map, &self.addresses.pool)?.data)?;
+        let mint_account = get_account(account_map, &self.addresses.token_mint)?;
+        let mint: Mint = decode(mint_account)?;
+        let token_vault: TokenAccount = decode(get_account(account_map, &self.addresses.pool_token_account)?)?;
+        let global_config = decode_global_config(&get_account(account_map, &self.global_config)?.data)?;
+        let clock = decode_clock(get_account(account_map, &sysvar::clock::ID)?)?;
+
+        let quote_vault_balance = if self.addresses.is_native_quote() {
+            let sol_vault = get_account(account_map, &self.addresses.sol_vault)?;
+            sol_vault.lamports.saturating_sub(Rent::default().minimum_balance(0))
+        } else {
+            self.addresses.quote_token_program = get_account(account_map, &self.addresses.quote_mint)?.owner;
+            decode::<TokenAccount>(get_account(account_map, &self.addresses.quote_vault)?)?.amount
+        };
+
+        self.pool = pool;
+        self.addresses.token_program = mint_account.owner;
+        self.snapshot = Some(PoolSnapshot {
+            mint_data: mint_account.data.clone(),
+            epoch: clock.epoch,
+            has_transfer_hook: has_transfer_hook(mint_account)?,
+            token_decimals: mint.decimals,
+            is_paused: global_config.is_paused,
+            token_vault_balance: token_vault.amount,
+            quote_vault_balance,
+        });
+
+        Ok(())
+    }
+
+    fn quote(&self, quote_params: &QuoteParams) -> Result<Quote> {
+        let snapshot = self.snapshot()?;
+        let direction = self.direction(&quote_params.input_mint, &quote_params.output_mint)?;
+
+        ensure!(!snapshot.is_paused, "protocol is paused");
+        ensure!(self.pool.is_active, "pool {} is inactive", self.addresses.pool);
+        ensure!(self.pool.status == PoolStatus::Trading, "pool {} has completed its curve", self.addresses.pool);
+        ensure!(
+            !snapshot.has_transfer_hook,
+            "mint {} has a transfer hook, whose extra accounts are not resolved here",
+            self.pool.token_mint
+        );
+
+        // Amounts are what the user's accounts send and receive; the program
+        // applies the mint's transfer fee between them and the vault.
+        let mode = match quote_params.swap_mode {
+            SwapMode::ExactIn => program_mode(SwapMode::ExactIn, quote_params.amount, 0),
+            SwapMode::ExactOut => program_mode(SwapMode::ExactOut, u64::MAX, quote_params.amount),
+        };
+        let swap = quote_detailed(&self.pool, snapshot.token_decimals, direction, mode, &snapshot.transfer_fee())?;
+
+        // The program pays out of the vaults, so a pool whose tracked reserves
+        // run ahead of its balances cannot settle the swap.
+        match direction {
+            SwapDirection::Buy => ensure!(
+                swap.token_amount <= snapshot.token_vault_balance,
+                "token vault of pool {} cannot cover the swap",
+                self.addresses.pool
+            ),
+            SwapDirection::Sell => ensure!(
+                swap.curve_sol_amount <= snapshot.quote_vault_balance,
+                "quote vault of pool {} cannot cover the swap",
+                self.addresses.pool
+            ),
+        }
+
+        Ok(Quote {
+            in_amount: swap.amount_in(direction),
+            out_amount: swap.amount_out(direction),
+            fee_amount: swap.fee_amount,
+            fee_mint: self.pool.quote_mint,
+            price_impact_bps: swap.price_impact_bps,
+        })
+    }
+
+    fn get_swap_and_account_metas(&self, swap_params: &SwapParams) -> Result<SwapAndAccountMetas> {
+        self.snapshot()?;
+        let direction = self.direction(&swap_params.source_mint, &swap_params.destination_mint)?;
+
+        let (user_token_account, user_quote_account) = match direction {
+            SwapDirection::Buy => (swap_params.destination_token_account, swap_params.source_token_account),
+            SwapDirection::Sell => (swap_params.source_token_account, swap_params.destination_token_account),
+        };
+
+        let fee_recipients = SwapFeeRecipients {
+            creator: self.pool.authority,
+            referrer: None,
+        };
+        let mode = program_mode(swap_params.swap_mode, swap_params.in_amount, swap_params.out_amount);
+
+        let instruction = if self.addresses.is_native_quote() {
+            swap_wsol(
+                swap_params.token_transfer_authority,
+                &self.addresses,
+                user_token_account,
+                user_quote_account,
+                fee_recipients,
+                args::SwapWsol { direction, mode },
+            )
+        } else {
+            swap_v2(
+                swap_params.token_transfer_authority,
+                &self.addresses,
+                user_token_account,
+                Some(user_quote_account),
+                fee_recipients,
+                args::SwapV2 { direction, mode },
+            )
+        };
+
+        Ok(SwapAndAccountMetas {
+            data: instruction.data,
+            account_metas: instruction.accounts,
+        })
+    }
+
+    fn clone_amm(&self) -> Box<dyn Amm + Send + Sync> {
+        Box::new(self.clone())
+    }
+}
@@ -0,0 +1,267 @@
+// adapter/tests/fixtures/mod.rs
+use anchor_lang::prelude::{AccountInfo, Clock, Pubkey, Rent, SolanaSysvar};
+use anchor_lang::solana_program::program_option::COption;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::AccountSerialize;
+use anchor_spl::token::spl_token;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::{TransferFee, TransferFeeConfig};
+use anchor_spl::token_2022::spl_token_2022::extension::{ExtensionType, StateWithExtensionsMut};
+use prism_liquidity_pool_adapter::{Account, AccountMap, KeyedAccount};
+use prism_liquidity_pool_sdk::pda::{find_global_config, PoolAddresses};
+use prism_liquidity_pool_sdk::state::{
+    CompletionThreshold, CurveKind, GlobalConfig, LiquidityPool, PoolCreationPolicy, PoolStatus, NATIVE_QUOTE_MINT,
+};
+use prism_liquidity_pool_sdk::{Price, ID};
+
+pub const TOKEN_DECIMALS: u8 = 6;
+pub const REAL_SOL: u64 = 5_000_000_000;
+pub const REAL_TOKEN: u64 = 200_000_000_000;
+pub const EPOCH: u64 = 500;
+
+/// A constant-product launch pool mid-curve, with its mint, vaults and the
+/// global config, as an aggregator would fetch them.
+pub struct PoolFixture {
+    pub addresses: PoolAddresses,
+    /// Token-2022 transfer fee of the mint, in basis points; `None` for a
+    /// legacy SPL mint.
+    pub transfer_fee_basis_points: Option<u16>,
+    pub pool: LiquidityPool,
+    pub global_config: GlobalConfig,
+    pub token_vault_balance: u64,
+    /// Lamports of the SOL vault, or the quote vault's token balance for
+    /// pools quoted in an SPL token.
+    pub quote_vault_balance: u64,
+}
+
+impl PoolFixture {
+    pub fn new() -> Self {
+        let token_mint = Pubkey::new_unique();
+
+        Self {
+            addresses: PoolAddresses::new(token_mint, spl_token::ID),
+            transfer_fee_basis_points: None,
+            pool: LiquidityPool {
+                authority: Pubkey::new_unique(),
+                token_mint,
+                quote_mint: NATIVE_QUOTE_MINT,
+                pool_index: 0,
+                exchange_rate: 27_958,
+                virtual_sol: 30_000_000_000,
+                virtual_token: 1_073_000_000_000_000,
+                real_sol: REAL_SOL,
+                real_token: REAL_TOKEN,
+                is_active: true,
+                created_at: 1_700_000_000,
+                fee_basis_points: 100,
+                bump: 255,
+                total_volume_sol: 0,
+                total_volume_token: 0,
+                trade_count: 0,
+                last_trade_timestamp: 0,
+                current_price: Price::ZERO,
+                curve: CurveKind::ConstantProduct,
+                tokens_sold: 0,
+                status: PoolStatus::Trading,
+                completion_threshold: CompletionThreshold::None,
+                sol_vault_bump: 255,
+                price_cumulative: 0,
+                price_last_updated: 1_700_000_000,
+            },
+            global_config: GlobalConfig {
+                admin: Pubkey::new_unique(),
+                treasury: Pubkey::new_unique(),
+                protocol_share_bps: 5_000,
+                creator_share_bps: 5_000,
+                referrer_share_bps: 0,
+                default_fee_basis_points: 100,
+                pool_creation_policy: PoolCreationPolicy::Permissionless,
+                is_paused: false,
+                bump: 255,
+            },
+            token_vault_balance: REAL_TOKEN,
+            quote_vault_balance: REAL_SOL + Rent::default().minimum_balance(0),
+        }
+    }
+
+    /// The same pool over a Token-2022 mint charging `basis_points` per transfer.
+    pub fn with_transfer_fee(mut self, basis_points: u16) -> Self {
+        self.addresses.token_program = spl_token_2022::ID;
+        self.transfer_fee_basis_points = Some(basis_points);
+        self
+    }
+
+    /// Another SOL pool of the same mint, at `pool_index`.
+    pub fn with_pool_index(mut self, pool_index: u8) -> Self {
+        self.addresses = PoolAddresses::with_quote(
+            self.addresses.token_mint,
+            self.addresses.token_program,
+            self.addresses.quote_mint,
+            self.addresses.quote_token_program,
+            pool_index,
+        );
+        self.pool.pool_index = pool_index;
+        self
+    }
+
+    /// The same pool quoted in a legacy SPL mint instead of SOL.
+    pub fn with_quote_mint(mut self) -> Self {
+        let quote_mint = Pubkey::new_unique();
+
+        self.addresses = PoolAddresses::with_quote(
+            self.addresses.token_mint,
+            self.addresses.token_program,
+            quote_mint,
+            spl_token::ID,
+            self.pool.pool_index,
+        );
+        self.pool.quote_mint = quote_mint;
+        self.quote_vault_balance = REAL_SOL;
+        self
+    }
+
+    pub fn keyed_pool(&self) -> KeyedAccount {
+        KeyedAccount {
+            key: self.addresses.pool,
+            account: program_account(&self.pool),
+        }
+    }
+
+    pub fn account_map(&self) -> AccountMap {
+        let token_vault = token_account_data(self.addresses.token_mint, self.addresses.pool, self.token_vault_balance);
+
+        let mut account_map = AccountMap::from([
+            (self.addresses.pool, program_account(&self.pool)),
+            (self.addresses.token_mint, self.token_program_account(self.mint_data())),
+            (self.addresses.pool_token_account, self.token_program_account(token_vault)),
+            (find_global_config().0, program_account(&self.global_config)),
+            (sysvar::clock::ID, clock_account()),
+        ]);
+
+        if self.addresses.is_native_quote() {
+            account_map.insert(
+                self.addresses.sol_vault,
+                Account {
+                    lamports: self.quote_vault_balance,
+                    data: Vec::new(),
+                    owner: anchor_lang::system_program::ID,
+                },
+            );
+        } else {
+            let mut quote_mint = vec![0; spl_token::state::Mint::LEN];
+            spl_token::state::Mint {
+                mint_authority: COption::None,
+                supply: 1_000_000_000_000_000,
+                decimals: 9,
+                is_initialized: true,
+                freeze_authority: COption::None,
+            }
+            .pack_into_slice(&mut quote_mint);
+            let quote_vault = token_account_data(self.addresses.quote_mint, self.addresses.pool, self.quote_vault_balance);
+
+            account_map.insert(self.addresses.quote_mint, spl_token_account(quote_mint));
+            account_map.insert(self.addresses.quote_vault, spl_token_account(quote_vault));
+        }
+
+        account_map
+    }
+
+    fn mint_data(&self) -> Vec<u8> {
+        let mint = spl_token_2022::state::Mint {
+            mint_authority: COption::None,
+            supply: 1_000_000_000_000_000,
+            decimals: TOKEN_DECIMALS,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        };
+
+        let Some(basis_points) = self.transfer_fee_basis_points else {
+            let mut data = vec![0; spl_token_2022::state::Mint::LEN];
+            mint.pack_into_slice(&mut data);
+            return data;
+        };
+
+        let space =
+            ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&[ExtensionType::TransferFeeConfig])
+                .unwrap();
+        let mut data = vec![0; space];
+        let mut state = StateWithExtensionsMut::<spl_token_2022::state::Mint>::unpack_uninitialized(&mut data).unwrap();
+        let transfer_fee = TransferFee {
+            epoch: 0.into(),
+            maximum_fee: u64::MAX.into(),
+            transfer_fee_basis_points: basis_points.into(),
+        };
+        let config = state.init_extension::<TransferFeeConfig>(true).unwrap();
+        config.older_transfer_fee = transfer_fee;
+        config.newer_transfer_fee = transfer_fee;
+        state.base = mint;
+        state.pack_base();
+        state.init_account_type().unwrap();
+
+        data
+    }
+
+    fn token_program_account(&self, data: Vec<u8>) -> Account {
+        Account {
+            lamports: 1_000_000,
+            data,
+            owner: self.addresses.token_program,
+        }
+    }
+}
+
+fn token_account_data(mint: Pubkey, owner: Pubkey, amount: u64) -> Vec<u8> {
+    let mut data = vec![0; spl_token::state::Account::LEN];
+    spl_token::state::Account {
+        mint,
+        owner,
+        amount,
+        delegate: COption::None,
+        state: spl_token::state::AccountState::Initialized,
+        is_native: COption::None,
+        delegated_amount: 0,
+        close_authority: COption::None,
+    }
+    .pack_into_slice(&mut data);
+
+    data
+}
+
+fn spl_token_account(data: Vec<u8>) -> Account {
+    Account {
+        lamports: 1_000_000,
+        data,
+        owner: spl_token::ID,
+    }
+}
+
+fn program_account<T: AccountSerialize>(state: &T) -> Account {
+    let mut data = Vec::new();
+    state.try_serialize(&mut data).unwrap();
+
+    Account {
+        lamports: 1_000_000,
+        data,
+        owner: ID,
+    }
+}
+
+fn clock_account() -> Account {
+    let clock = Clock {
+        epoch: EPOCH,
+        ..Clock::default()
+    };
+    let mut lamports = 1_000_000;
+    let mut data = vec![0; Clock::size_of()];
+    let owner = sysvar::ID;
+    let mut account_info =
+        AccountInfo::new(&sysvar::clock::ID, false, false, &mut lamports, &mut data, &owner, false, 0);
+    clock.to_account_info(&mut account_info).unwrap();
+
+    Account {
+        lamports: 1_000_000,
+        data,
+        owner,
+    }
+}
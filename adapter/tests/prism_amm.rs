@@ -0,0 +1,357 @@
+// adapter/tests/prism_amm.rs
+mod fixtures;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use anchor_spl::token::spl_token::native_mint;
+use anchor_spl::token_2022::spl_token_2022;
+use fixtures::{PoolFixture, TOKEN_DECIMALS};
+use prism_liquidity_pool_adapter::{Amm, PrismAmm, QuoteParams, SwapMode, SwapParams};
+use prism_liquidity_pool_sdk::instructions::{swap_v2, swap_wsol, SwapFeeRecipients};
+use prism_liquidity_pool_sdk::liquidity_pool::utils::quote::simulate_swap;
+use prism_liquidity_pool_sdk::pda::find_swap_wsol_account;
+use prism_liquidity_pool_sdk::quote::NoTransferFee;
+use prism_liquidity_pool_sdk::{args, state, ID};
+
+fn loaded_amm(fixture: &PoolFixture) -> PrismAmm {
+    let mut amm = PrismAmm::from_keyed_account(&fixture.keyed_pool()).unwrap();
+    amm.update(&fixture.account_map()).unwrap();
+    amm
+}
+
+fn buy(amount: u64, swap_mode: SwapMode, fixture: &PoolFixture) -> QuoteParams {
+    QuoteParams {
+        amount,
+        input_mint: fixture.pool.quote_mint,
+        output_mint: fixture.addresses.token_mint,
+        swap_mode,
+    }
+}
+
+fn sell(amount: u64, swap_mode: SwapMode, fixture: &PoolFixture) -> QuoteParams {
+    QuoteParams {
+        amount,
+        input_mint: fixture.addresses.token_mint,
+        output_mint: fixture.pool.quote_mint,
+        swap_mode,
+    }
+}
+
+#[test]
+fn loads_pool_and_lists_accounts() {
+    let fixture = PoolFixture::new();
+    let amm = PrismAmm::from_keyed_account(&fixture.keyed_pool()).unwrap();
+
+    assert_eq!(amm.key(), fixture.addresses.pool);
+    assert_eq!(amm.program_id(), ID);
+    assert_eq!(amm.get_reserve_mints(), vec![native_mint::ID, fixture.addresses.token_mint]);
+
+    let accounts = amm.get_accounts_to_update();
+    let account_map = fixture.account_map();
+    assert_eq!(accounts.len(), account_map.len());
+    assert!(accounts.iter().all(|key| account_map.contains_key(key)));
+}
+
+#[test]
+fn rejects_foreign_and_misplaced_accounts() {
+    let fixture = PoolFixture::new();
+
+    let mut keyed_pool = fixture.keyed_pool();
+    keyed_pool.account.owner = Pubkey::new_unique();
+    assert!(PrismAmm::from_keyed_account(&keyed_pool).is_err());
+
+    let mut keyed_pool = fixture.keyed_pool();
+    keyed_pool.key = Pubkey::new_unique();
+    assert!(PrismAmm::from_keyed_account(&keyed_pool).is_err());
+}
+
+#[test]
+fn loads_pools_at_any_index() {
+    let fixture = PoolFixture::new();
+    let first_pool = fixture.addresses.pool;
+    let fixture = fixture.with_pool_index(1);
+    assert_ne!(fixture.addresses.pool, first_pool);
+
+    let amm = loaded_amm(&fixture);
+    assert_eq!(amm.key(), fixture.addresses.pool);
+
+    // A pool decoded at another index's address is not its PDA.
+    let mut keyed_pool = fixture.keyed_pool();
+    keyed_pool.key = first_pool;
+    assert!(PrismAmm::from_keyed_account(&keyed_pool).is_err());
+}
+
+#[test]
+fn quote_requires_update() {
+    let fixture = PoolFixture::new();
+    let amm = PrismAmm::from_keyed_account(&fixture.keyed_pool()).unwrap();
+
+    assert!(amm.quote(&buy(1_000_000_000, SwapMode::ExactIn, &fixture)).is_err());
+}
+
+#[test]
+fn quotes_match_program() {
+    let fixture = PoolFixture::new();
+    let amm = loaded_amm(&fixture);
+
+    let cases = [
+        (buy(1_000_000_000, SwapMode::ExactIn, &fixture), state::SwapDirection::Buy, state::SwapMode::ExactIn { amount_in: 1_000_000_000, min_amount_out: 0 }),
+        (buy(5_000_000_000, SwapMode::ExactOut, &fixture), state::SwapDirection::Buy, state::SwapMode::ExactOut { amount_out: 5_000_000_000, max_amount_in: u64::MAX }),
+        (sell(10_000_000_000, SwapMode::ExactIn, &fixture), state::SwapDirection::Sell, state::SwapMode::ExactIn { amount_in: 10_000_000_000, min_amount_out: 0 }),
+        (sell(100_000_000, SwapMode::ExactOut, &fixture), state::SwapDirection::Sell, state::SwapMode::ExactOut { amount_out: 100_000_000, max_amount_in: u64::MAX }),
+    ];
+
+    for (params, direction, mode) in cases {
+        let quote = amm.quote(&params).unwrap();
+        let expected = simulate_swap(&fixture.pool, TOKEN_DECIMALS, direction, mode, &NoTransferFee).unwrap();
+
+        assert_eq!(quote.in_amount, expected.amount_in);
+        assert_eq!(quote.out_amount, expected.amount_out);
+        assert_eq!(quote.fee_amount, expected.fee_amount);
+        assert_eq!(quote.price_impact_bps, expected.price_impact_bps);
+        assert_eq!(quote.fee_mint, native_mint::ID);
+    }
+}
+
+#[test]
+fn exact_out_round_trips_exact_in() {
+    let fixture = PoolFixture::new();
+    let amm = loaded_amm(&fixture);
+
+    let exact_in = amm.quote(&buy(2_500_000_000, SwapMode::ExactIn, &fixture)).unwrap();
+    let exact_out = amm.quote(&buy(exact_in.out_amount, SwapMode::ExactOut, &fixture)).unwrap();
+
+    assert_eq!(exact_out.out_amount, exact_in.out_amount);
+    assert!(exact_out.in_amount <= exact_in.in_amount);
+}
+
+#[test]
+fn transfer_fee_mints_quote_net_amounts() {
+    let fixture = PoolFixture::new();
+    let plain = loaded_amm(&fixture);
+    let fixture = fixture.with_transfer_fee(100);
+    let amm = loaded_amm(&fixture);
+    let transfer_fee = |amount: u64| (amount * 100).div_ceil(10_000);
+
+    let bought = plain.quote(&buy(1_000_000_000, SwapMode::ExactIn, &fixture)).unwrap();
+    let quote = amm.quote(&buy(1_000_000_000, SwapMode::ExactIn, &fixture)).unwrap();
+    assert_eq!(quote.in_amount, bought.in_amount);
+    assert_eq!(quote.out_amount, bought.out_amount - transfer_fee(bought.out_amount));
+
+    let sold = plain.quote(&sell(9_900_000_000, SwapMode::ExactIn, &fixture)).unwrap();
+    let quote = amm.quote(&sell(10_000_000_000, SwapMode::ExactIn, &fixture)).unwrap();
+    assert_eq!(quote.in_amount, 10_000_000_000);
+    assert_eq!(quote.out_amount, sold.out_amount);
+
+    let quote = amm.quote(&buy(5_000_000_000, SwapMode::ExactOut, &fixture)).unwrap();
+    assert_eq!(quote.out_amount, 5_000_000_000);
+    assert!(quote.in_amount > plain.quote(&buy(5_000_000_000, SwapMode::ExactOut, &fixture)).unwrap().in_amount);
+
+    let user = Pubkey::new_unique();
+    let swap = amm
+        .get_swap_and_account_metas(&SwapParams {
+            source_mint: native_mint::ID,
+            destination_mint: fixture.addresses.token_mint,
+            source_token_account: user,
+            destination_token_account: Pubkey::new_unique(),
+            token_transfer_authority: user,
+            in_amount: quote.in_amount,
+            out_amount: 5_000_000_000,
+            swap_mode: SwapMode::ExactOut,
+        })
+        .unwrap();
+
+    assert!(swap.account_metas.iter().any(|meta| meta.pubkey == spl_token_2022::ID));
+    // The program grosses the vault's output up for the fee itself.
+    let decoded = args::SwapWsol::deserialize(&mut &swap.data[8..]).unwrap();
+    assert_eq!(
+        decoded.mode,
+        state::SwapMode::ExactOut { amount_out: 5_000_000_000, max_amount_in: quote.in_amount }
+    );
+}
+
+#[test]
+fn rejects_unknown_mints() {
+    let fixture = PoolFixture::new();
+    let amm = loaded_amm(&fixture);
+
+    let mut params = buy(1_000_000_000, SwapMode::ExactIn, &fixture);
+    params.output_mint = Pubkey::new_unique();
+    assert!(amm.quote(&params).is_err());
+
+    let mut params = sell(1_000_000_000, SwapMode::ExactIn, &fixture);
+    params.output_mint = fixture.addresses.token_mint;
+    assert!(amm.quote(&params).is_err());
+}
+
+#[test]
+fn rejects_untradeable_pools() {
+    let mut fixture = PoolFixture::new();
+    fixture.global_config.is_paused = true;
+    assert!(loaded_amm(&fixture).quote(&buy(1_000_000_000, SwapMode::ExactIn, &fixture)).is_err());
+
+    let mut fixture = PoolFixture::new();
+    fixture.pool.is_active = false;
+    assert!(loaded_amm(&fixture).quote(&buy(1_000_000_000, SwapMode::ExactIn, &fixture)).is_err());
+
+    let mut fixture = PoolFixture::new();
+    fixture.pool.status = state::PoolStatus::Completed;
+    assert!(loaded_amm(&fixture).quote(&buy(1_000_000_000, SwapMode::ExactIn, &fixture)).is_err());
+}
+
+#[test]
+fn rejects_quotes_the_vaults_cannot_settle() {
+    let fixture = PoolFixture::new();
+    let quote = loaded_amm(&fixture).quote(&sell(100_000_000, SwapMode::ExactOut, &fixture)).unwrap();
+
+    let mut fixture = PoolFixture::new();
+    fixture.quote_vault_balance -= fixture.pool.real_sol;
+    assert!(loaded_amm(&fixture).quote(&sell(quote.in_amount, SwapMode::ExactIn, &fixture)).is_err());
+
+    let mut fixture = PoolFixture::new();
+    fixture.token_vault_balance = 0;
+    assert!(loaded_amm(&fixture).quote(&buy(1_000_000_000, SwapMode::ExactIn, &fixture)).is_err());
+}
+
+#[test]
+fn update_picks_up_new_pool_state() {
+    let mut fixture = PoolFixture::new();
+    let mut amm = loaded_amm(&fixture);
+    let before = amm.quote(&buy(1_000_000_000, SwapMode::ExactIn, &fixture)).unwrap();
+
+    fixture.pool.real_sol += 10_000_000_000;
+    fixture.quote_vault_balance += 10_000_000_000;
+    amm.update(&fixture.account_map()).unwrap();
+    let after = amm.quote(&buy(1_000_000_000, SwapMode::ExactIn, &fixture)).unwrap();
+
+    assert!(after.out_amount < before.out_amount);
+}
+
+#[test]
+fn swap_account_metas_match_program_accounts() {
+    let fixture = PoolFixture::new();
+    let amm = loaded_amm(&fixture);
+    let user = Pubkey::new_unique();
+    let user_token_account = Pubkey::new_unique();
+    let user_wsol_account = Pubkey::new_unique();
+
+    let swap = amm
+        .get_swap_and_account_metas(&SwapParams {
+            source_mint: fixture.addresses.token_mint,
+            destination_mint: native_mint::ID,
+            source_token_account: user_token_account,
+            destination_token_account: user_wsol_account,
+            token_transfer_authority: user,
+            in_amount: 10_000_000_000,
+            out_amount: 250_000_000,
+            swap_mode: SwapMode::ExactIn,
+        })
+        .unwrap();
+
+    let expected_args = args::SwapWsol {
+        direction: state::SwapDirection::Sell,
+        mode: state::SwapMode::ExactIn { amount_in: 10_000_000_000, min_amount_out: 250_000_000 },
+    };
+    let expected = swap_wsol(
+        user,
+        &fixture.addresses,
+        user_token_account,
+        user_wsol_account,
+        SwapFeeRecipients {
+            creator: fixture.pool.authority,
+            referrer: None,
+        },
+        args::SwapWsol { ..expected_args },
+    );
+
+    assert_eq!(swap.account_metas, expected.accounts);
+    assert_eq!(swap.data, expected.data);
+
+    assert_eq!(&swap.data[..8], &args::SwapWsol::DISCRIMINATOR);
+    let decoded = args::SwapWsol::deserialize(&mut &swap.data[8..]).unwrap();
+    assert_eq!(decoded.direction, expected_args.direction);
+    assert_eq!(decoded.mode, expected_args.mode);
+
+    let signers: Vec<_> = swap.account_metas.iter().filter(|meta| meta.is_signer).collect();
+    assert_eq!(signers.len(), 1);
+    assert_eq!(signers[0].pubkey, user);
+    assert!(swap
+        .account_metas
+        .iter()
+        .any(|meta| meta.pubkey == user_token_account && meta.is_writable));
+    assert!(swap
+        .account_metas
+        .iter()
+        .any(|meta| meta.pubkey == user_wsol_account && meta.is_writable));
+    assert!(swap
+        .account_metas
+        .iter()
+        .all(|meta| meta.pubkey != find_swap_wsol_account(&fixture.addresses.pool, &user).0));
+}
+
+#[test]
+fn quote_mint_pools_settle_in_quote_token_accounts() {
+    let fixture = PoolFixture::new().with_quote_mint();
+    let amm = loaded_amm(&fixture);
+    let quote_mint = fixture.pool.quote_mint;
+
+    assert_eq!(amm.get_reserve_mints(), vec![quote_mint, fixture.addresses.token_mint]);
+    let accounts = amm.get_accounts_to_update();
+    let account_map = fixture.account_map();
+    assert_eq!(accounts.len(), account_map.len());
+    assert!(accounts.iter().all(|key| account_map.contains_key(key)));
+
+    let mut params = buy(1_000_000_000, SwapMode::ExactIn, &fixture);
+    let quote = amm.quote(&params).unwrap();
+    let expected = simulate_swap(
+        &fixture.pool,
+        TOKEN_DECIMALS,
+        state::SwapDirection::Buy,
+        state::SwapMode::ExactIn { amount_in: 1_000_000_000, min_amount_out: 0 },
+        &NoTransferFee,
+    )
+    .unwrap();
+    assert_eq!(quote.out_amount, expected.amount_out);
+    assert_eq!(quote.fee_mint, quote_mint);
+
+    params.input_mint = native_mint::ID;
+    assert!(amm.quote(&params).is_err());
+
+    let user = Pubkey::new_unique();
+    let user_token_account = Pubkey::new_unique();
+    let user_quote_account = Pubkey::new_unique();
+    let swap = amm
+        .get_swap_and_account_metas(&SwapParams {
+            source_mint: fixture.addresses.token_mint,
+            destination_mint: quote_mint,
+            source_token_account: user_token_account,
+            destination_token_account: user_quote_account,
+            token_transfer_authority: user,
+            in_amount: 10_000_000_000,
+            out_amount: 250_000_000,
+            swap_mode: SwapMode::ExactIn,
+        })
+        .unwrap();
+
+    let expected = swap_v2(
+        user,
+        &fixture.addresses,
+        user_token_account,
+        Some(user_quote_account),
+        SwapFeeRecipients {
+            creator: fixture.pool.authority,
+            referrer: None,
+        },
+        args::SwapV2 {
+            direction: state::SwapDirection::Sell,
+            mode: state::SwapMode::ExactIn { amount_in: 10_000_000_000, min_amount_out: 250_000_000 },
+        },
+    );
+
+    assert_eq!(swap.account_metas, expected.accounts);
+    assert_eq!(swap.data, expected.data);
+    assert!(swap
+        .account_metas
+        .iter()
+        .any(|meta| meta.pubkey == fixture.addresses.quote_vault && meta.is_writable));
+}
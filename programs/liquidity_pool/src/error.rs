@@ -30,9 +30,54 @@ pub enum LiquidityPoolError {
     #[msg("Slippage tolerance exceeded")]
     SlippageExceeded,
 
-    #[msg("Pool already exists for this token")]
+    #[msg("Pool already exists for this token, quote mint and pool index")]
     PoolAlreadyExists,
 
     #[msg("Invalid swap parameters")]
     InvalidSwapParams,
+
+    #[msg("Fee shares must sum to 10000 basis points")]
+    InvalidFeeShares,
+
+    #[msg("No fees available to claim")]
+    NoFeesToClaim,
+
+    #[msg("Protocol is paused")]
+    ProtocolPaused,
+
+    #[msg("Creator is not allowlisted to create pools")]
+    CreatorNotAllowed,
+
+    #[msg("Token decimals do not match the mint")]
+    InvalidTokenDecimals,
+
+    #[msg("Invalid bonding curve parameters")]
+    InvalidCurveParams,
+
+    #[msg("Bonding curve has completed")]
+    CurveCompleted,
+
+    #[msg("Pool is not in the required status")]
+    InvalidPoolStatus,
+
+    #[msg("Pool still holds unclaimed fees")]
+    PoolNotEmpty,
+
+    #[msg("TWAP window is not covered by oracle observations")]
+    InvalidOracleWindow,
+
+    #[msg("Token mint has an unsupported Token-2022 extension")]
+    UnsupportedMintExtension,
+
+    #[msg("Pool vault received less than the transfer required")]
+    VaultTransferShortfall,
+
+    #[msg("Quote mint is not supported here")]
+    UnsupportedQuoteMint,
+
+    #[msg("Pools quoted in an SPL token require their quote token accounts")]
+    MissingQuoteAccounts,
+
+    #[msg("Liquidity providers still hold pool shares")]
+    LiquidityOutstanding,
 }
\ No newline at end of file
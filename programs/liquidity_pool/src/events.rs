@@ -1,5 +1,7 @@
 // programs/liquidity_pool/src/events.rs
 use anchor_lang::prelude::*;
+use crate::state::{CompletionThreshold, CurveKind, PoolCreationPolicy};
+use crate::utils::math::Price;
 
 #[event]
 pub struct SwapExecuted {
@@ -9,28 +11,54 @@ pub struct SwapExecuted {
     pub trader: Pubkey,
     #[index]
     pub token_mint: Pubkey,
+    pub quote_mint: Pubkey,
     pub dex_name: String,
     pub trade_direction: String,
     pub token_amount: u64,
     pub sol_amount: u64,
-    pub exchange_rate: f64,
+    pub exchange_rate: Price,
     pub timestamp: i64,
     pub transaction_type: String,
     pub pool_type: String,
-    pub slippage: f64,
+    pub slippage_bps: u64,
+    /// In quote units (lamports for SOL pools) for both directions.
     pub fee_amount: u64,
     pub program_version: String,
     pub network: String,
 }
 
+/// One `swap_via_sol` route: `amount_in` of `input_mint` sold into
+/// `input_pool`, and the `sol_amount` of `quote_mint` it returned spent on
+/// `output_mint` from `output_pool`. Each leg also emits its own
+/// `SwapExecuted`.
+#[event]
+pub struct RoutedSwapExecuted {
+    #[index]
+    pub trader: Pubkey,
+    pub input_pool: Pubkey,
+    pub input_mint: Pubkey,
+    pub output_pool: Pubkey,
+    pub output_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub amount_in: u64,
+    pub sol_amount: u64,
+    pub amount_out: u64,
+    pub dex_name: String,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct PoolCreated {
     pub pool: Pubkey,
     pub authority: Pubkey,
     pub token_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub pool_index: u8,
     pub initial_exchange_rate: u64,
     pub initial_sol: u64,
     pub initial_tokens: u64,
+    pub curve: CurveKind,
+    pub completion_threshold: CompletionThreshold,
     pub dex_name: String,
     pub pool_type: String,
     pub version: String,
@@ -40,8 +68,10 @@ pub struct PoolCreated {
 pub struct LiquidityAdded {
     pub pool: Pubkey,
     pub liquidity_provider: Pubkey,
+    pub quote_mint: Pubkey,
     pub sol_amount: u64,
     pub token_amount: u64,
+    pub lp_amount: u64,
     pub dex_name: String,
     pub transaction_type: String,
 }
@@ -50,8 +80,10 @@ pub struct LiquidityAdded {
 pub struct LiquidityRemoved {
     pub pool: Pubkey,
     pub liquidity_provider: Pubkey,
+    pub quote_mint: Pubkey,
     pub sol_amount: u64,
     pub token_amount: u64,
+    pub lp_amount: u64,
     pub dex_name: String,
     pub transaction_type: String,
 }
@@ -77,4 +109,91 @@ pub struct PoolClosed {
     pub authority: Pubkey,
     pub final_sol_reserve: u64,
     pub final_token_reserve: u64,
+}
+
+#[event]
+pub struct FeeUpdated {
+    pub pool: Pubkey,
+    pub authority: Pubkey,
+    pub old_fee_basis_points: u16,
+    pub new_fee_basis_points: u16,
+}
+
+#[event]
+pub struct FeesClaimed {
+    pub pool: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub total_claimed: u64,
+}
+
+#[event]
+pub struct FeesDistributed {
+    pub pool: Pubkey,
+    pub protocol_fee: u64,
+    pub creator: Pubkey,
+    pub creator_fee: u64,
+    pub referrer: Option<Pubkey>,
+    pub referrer_fee: u64,
+}
+
+#[event]
+pub struct GlobalConfigUpdated {
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+    pub protocol_share_bps: u16,
+    pub creator_share_bps: u16,
+    pub referrer_share_bps: u16,
+    pub default_fee_basis_points: u16,
+    pub pool_creation_policy: PoolCreationPolicy,
+    pub is_paused: bool,
+}
+
+#[event]
+pub struct CreatorAllowlistUpdated {
+    pub creator: Pubkey,
+    pub allowed: bool,
+}
+
+#[event]
+pub struct CurveCompleted {
+    pub pool: Pubkey,
+    pub token_mint: Pubkey,
+    pub real_sol_reserve: u64,
+    pub tokens_sold: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PoolMigrated {
+    pub pool: Pubkey,
+    pub token_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub caller: Pubkey,
+    pub amm_pool: Pubkey,
+    pub sol_amount: u64,
+    pub token_amount: u64,
+    /// Launch-pool tokens beyond what the AMM needed at the curve's final price.
+    pub burned_token_amount: u64,
+    pub locked_lp_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReservesSynced {
+    pub pool: Pubkey,
+    pub caller: Pubkey,
+    pub sol_amount: u64,
+    pub token_amount: u64,
+    pub real_sol: u64,
+    pub real_token: u64,
+}
+
+#[event]
+pub struct ReservesSkimmed {
+    pub pool: Pubkey,
+    pub authority: Pubkey,
+    pub recipient: Pubkey,
+    pub sol_amount: u64,
+    pub token_amount: u64,
 }
\ No newline at end of file
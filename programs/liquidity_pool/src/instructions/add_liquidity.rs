@@ -1,131 +1,260 @@
 // programs/liquidity_pool/src/instructions/add_liquidity.rs
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount, Mint, TransferChecked, transfer_checked};
+use anchor_spl::token_interface::{TokenInterface, TokenAccount, Mint, TransferChecked, MintTo, mint_to};
+use crate::utils::token::{current_gross_transfer_amount, current_transfer_fee, transfer_tokens_to_vault};
 use crate::state::*;
 use crate::error::*;
 use crate::events::*;
+use crate::utils::curve::Curve;
+use crate::utils::lp::calculate_deposit;
 use crate::utils::math::*;
+use crate::utils::validation::validate_token_decimals;
+use crate::utils::vault::{transfer_quote_in, QuoteTokenAccounts};
 
 #[derive(Accounts)]
 pub struct AddLiquidity<'info> {
     #[account(
         mut,
-        seeds = [b"pool", pool.token_mint.as_ref()],
+        seeds = [b"pool", pool.token_mint.as_ref(), pool.quote_mint.as_ref(), &[pool.pool_index]],
         bump = pool.bump
     )]
     pub pool: Account<'info, LiquidityPool>,
 
     #[account(mut)]
-    pub authority: Signer<'info>,
+    pub user: Signer<'info>,
 
-    pub token_mint: Account<'info, Mint>,
+    #[account(address = pool.token_mint @ LiquidityPoolError::InvalidSwapParams)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
-        constraint = authority_token_account.mint == token_mint.key(),
-        constraint = authority_token_account.owner == authority.key()
+        constraint = user_token_account.mint == token_mint.key(),
+        constraint = user_token_account.owner == user.key()
     )]
-    pub authority_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
-        seeds = [b"pool_token", token_mint.key().as_ref()],
+        seeds = [b"pool_token", pool.key().as_ref()],
         bump
     )]
-    pub pool_token_account: Account<'info, TokenAccount>,
+    pub pool_token_account: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_program: Program<'info, Token>,
+    #[account(
+        mut,
+        seeds = [b"sol_vault", pool.key().as_ref()],
+        bump = pool.sol_vault_bump
+    )]
+    pub sol_vault: SystemAccount<'info>,
+
+    #[account(address = pool.quote_mint @ LiquidityPoolError::UnsupportedQuoteMint)]
+    pub quote_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(
+        mut,
+        seeds = [b"quote_vault", pool.key().as_ref()],
+        bump
+    )]
+    pub quote_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        constraint = user_quote_account.mint == pool.quote_mint,
+        constraint = user_quote_account.owner == user.key()
+    )]
+    pub user_quote_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        seeds = [b"lp_mint", pool.key().as_ref()],
+        bump
+    )]
+    pub lp_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = user_lp_account.mint == lp_mint.key(),
+        constraint = user_lp_account.owner == user.key()
+    )]
+    pub user_lp_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"lp_lock", pool.key().as_ref()],
+        bump
+    )]
+    pub lp_lock_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Option<Interface<'info, TokenInterface>>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
 
-pub fn add_liquidity_handler(
-    ctx: Context<AddLiquidity>,
-    sol_amount: u64,
-    token_amount: u64,
+pub fn add_liquidity_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, AddLiquidity<'info>>,
+    max_sol_amount: u64,
+    max_token_amount: u64,
+    min_lp_amount: u64,
     token_decimals: u8,
+) -> Result<()> {
+    require!(token_decimals == ctx.accounts.token_mint.decimals, LiquidityPoolError::InvalidTokenDecimals);
+    add_liquidity_v2_handler(ctx, max_sol_amount, max_token_amount, min_lp_amount)
+}
+
+pub fn add_liquidity_v2_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, AddLiquidity<'info>>,
+    max_sol_amount: u64,
+    max_token_amount: u64,
+    min_lp_amount: u64,
 ) -> Result<()> {
     let pool = &mut ctx.accounts.pool;
-    let authority = &ctx.accounts.authority;
+    let user = &ctx.accounts.user;
+    let token_decimals = ctx.accounts.token_mint.decimals;
+    let lp_supply = ctx.accounts.lp_mint.supply;
+
+    require!(max_sol_amount > 0 || max_token_amount > 0, LiquidityPoolError::InvalidReserves);
+    require!(pool.status == PoolStatus::Trading, LiquidityPoolError::InvalidPoolStatus);
+    validate_token_decimals(token_decimals)?;
 
-    require!(sol_amount > 0 || token_amount > 0, LiquidityPoolError::InvalidReserves);
-    require!(token_decimals <= 18, LiquidityPoolError::InvalidSwapParams);
+    // Deposits are sized on what reaches the vault after any transfer fee;
+    // the depositor pays the fee on top.
+    let mint_info = ctx.accounts.token_mint.to_account_info();
+    let max_token_deposit = safe_sub(max_token_amount, current_transfer_fee(&mint_info, max_token_amount)?)?;
 
-    let normalized_token_amount = if token_amount > 0 {
-        normalize_token_amount(token_amount, token_decimals)?
+    // Later deposits are matched to the current reserve ratio and only the
+    // matched amounts are taken, so any excess stays with the depositor. The
+    // first deposit sets the ratio and is valued at the spot price, with
+    // MINIMUM_LIQUIDITY shares locked in the pool so the share price cannot be
+    // inflated by donating to an empty pool.
+    let (sol_amount, token_amount, lp_amount, locked_lp_amount) = if lp_supply > 0 {
+        let (lp_amount, sol_amount, token_amount) = calculate_deposit(
+            max_sol_amount,
+            max_token_deposit,
+            pool.real_sol,
+            pool.real_token,
+            lp_supply,
+        )?;
+
+        (sol_amount, token_amount, lp_amount, 0)
     } else {
-        0
+        let curve = pool.pricing_curve(token_decimals)?;
+
+        let deposit_value = (max_sol_amount as u128)
+            .checked_add(curve.market_cap(normalize_token_amount(max_token_deposit, token_decimals)?)?)
+            .ok_or(LiquidityPoolError::MathOverflow)?;
+        let total_lp_amount = u128_to_u64(deposit_value)?;
+
+        require!(total_lp_amount > MINIMUM_LIQUIDITY, LiquidityPoolError::InsufficientLiquidity);
+
+        (max_sol_amount, max_token_deposit, total_lp_amount - MINIMUM_LIQUIDITY, MINIMUM_LIQUIDITY)
     };
 
+    require!(lp_amount > 0, LiquidityPoolError::InvalidSwapAmount);
+    require!(lp_amount >= min_lp_amount, LiquidityPoolError::SlippageExceeded);
+
+    let normalized_token_amount = normalize_token_amount(token_amount, token_decimals)?;
+
     if token_amount > 0 {
+        let gross_token_amount = current_gross_transfer_amount(&mint_info, token_amount)?;
+
+        require!(gross_token_amount <= max_token_amount, LiquidityPoolError::SlippageExceeded);
         require!(
-            ctx.accounts.authority_token_account.amount >= token_amount,
+            ctx.accounts.user_token_account.amount >= gross_token_amount,
             LiquidityPoolError::InsufficientLiquidity
         );
 
         let transfer_tokens_ctx = CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
             TransferChecked {
-                from: ctx.accounts.authority_token_account.to_account_info(),
+                from: ctx.accounts.user_token_account.to_account_info(),
                 to: ctx.accounts.pool_token_account.to_account_info(),
-                mint: ctx.accounts.token_mint.to_account_info(),
-                authority: authority.to_account_info(),
+                mint: mint_info.clone(),
+                authority: user.to_account_info(),
             },
-        );
-        transfer_checked(transfer_tokens_ctx, token_amount, token_decimals)?;
+        )
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+        let received = transfer_tokens_to_vault(
+            transfer_tokens_ctx,
+            &mut ctx.accounts.pool_token_account,
+            gross_token_amount,
+            token_decimals,
+        )?;
+
+        require!(received >= token_amount, LiquidityPoolError::VaultTransferShortfall);
     }
 
     if sol_amount > 0 {
-        require!(
-            authority.lamports() >= sol_amount,
-            LiquidityPoolError::InsufficientLiquidity
-        );
+        let quote = QuoteTokenAccounts::load(
+            pool.is_native_quote(),
+            ctx.accounts.quote_mint.as_deref(),
+            ctx.accounts.quote_vault.as_deref(),
+            ctx.accounts.user_quote_account.as_deref(),
+            ctx.accounts.quote_token_program.as_ref(),
+        )?;
+        if quote.is_none() {
+            require!(
+                user.lamports() >= sol_amount,
+                LiquidityPoolError::InsufficientLiquidity
+            );
+        }
 
-        let transfer_sol_ctx = CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            anchor_lang::system_program::Transfer {
-                from: authority.to_account_info(),
-                to: pool.to_account_info(),
-            },
-        );
-        anchor_lang::system_program::transfer(transfer_sol_ctx, sol_amount)?;
+        transfer_quote_in(
+            quote.as_ref(),
+            &user.to_account_info(),
+            &ctx.accounts.sol_vault.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            sol_amount,
+        )?;
     }
 
-    if sol_amount > 0 {
-        require!(sol_amount <= pool.sol_reserve, LiquidityPoolError::InsufficientLiquidity);
-        pool.sol_reserve = pool.sol_reserve.checked_sub(sol_amount)
-            .ok_or(LiquidityPoolError::MathOverflow)?;
-    }
+    pool.real_sol = safe_add(pool.real_sol, sol_amount)?;
+    pool.real_token = safe_add(pool.real_token, token_amount)?;
 
-    if normalized_token_amount > 0 {
-        require!(normalized_token_amount <= pool.token_reserve, LiquidityPoolError::InsufficientLiquidity);
-        pool.token_reserve = pool.token_reserve.checked_sub(normalized_token_amount)
-            .ok_or(LiquidityPoolError::MathOverflow)?;
+    if pool.curve == CurveKind::ConstantProduct {
+        require!(sol_amount <= pool.virtual_sol, LiquidityPoolError::InsufficientLiquidity);
+        require!(normalized_token_amount <= pool.virtual_token, LiquidityPoolError::InsufficientLiquidity);
+        pool.virtual_sol = safe_sub(pool.virtual_sol, sol_amount)?;
+        pool.virtual_token = safe_sub(pool.virtual_token, normalized_token_amount)?;
     }
 
-    pool.exchange_rate = if pool.sol_reserve > 0 && pool.token_reserve > 0 {
-        if pool.token_reserve <= u64::MAX / 1_000_000 {
-            pool.token_reserve.checked_mul(1_000_000)
-                .ok_or(LiquidityPoolError::MathOverflow)?
-                .checked_div(pool.sol_reserve)
-                .unwrap_or(1)
-        } else {
-            pool.token_reserve.checked_div(pool.sol_reserve)
-                .ok_or(LiquidityPoolError::MathOverflow)?
-                .checked_mul(1_000_000)
-                .unwrap_or(1)
-        }
-    } else {
-        1
-    };
+    let seeds = pool.signer_seeds();
 
-    pool.is_active = true;
+    if locked_lp_amount > 0 {
+        mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.lp_mint.to_account_info(),
+                    to: ctx.accounts.lp_lock_account.to_account_info(),
+                    authority: pool.to_account_info(),
+                },
+                &[&seeds]
+            ),
+            locked_lp_amount,
+        )?;
+    }
+
+    mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                to: ctx.accounts.user_lp_account.to_account_info(),
+                authority: pool.to_account_info(),
+            },
+            &[&seeds]
+        ),
+        lp_amount,
+    )?;
 
     emit!(LiquidityAdded {
         pool: pool.key(),
-        liquidity_provider: authority.key(),
+        liquidity_provider: user.key(),
+        quote_mint: pool.quote_mint,
         sol_amount,
         token_amount,
+        lp_amount,
         dex_name: DEX_NAME.to_string(),
         transaction_type: "add_liquidity".to_string(),
     });
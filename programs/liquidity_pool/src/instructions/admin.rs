@@ -1,15 +1,19 @@
 // programs/liquidity_pool/src/instructions/admin.rs
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{burn, Burn, Mint, TokenAccount, TokenInterface, TransferChecked};
 use crate::state::*;
 use crate::error::*;
 use crate::events::*;
 use crate::utils::auth::check_authority;
+use crate::utils::token::{harvest_withheld_tokens, transfer_tokens};
+use crate::utils::validation::validate_fee;
+use crate::utils::vault::{close_pool_token_vault, transfer_from_sol_vault, QuoteTokenAccounts};
 
 #[derive(Accounts)]
 pub struct UpdateExchangeRate<'info> {
     #[account(
         mut,
-        seeds = [b"pool", pool.token_mint.as_ref()],
+        seeds = [b"pool", pool.token_mint.as_ref(), pool.quote_mint.as_ref(), &[pool.pool_index]],
         bump = pool.bump
     )]
     pub pool: Account<'info, LiquidityPool>,
@@ -21,7 +25,19 @@ pub struct UpdateExchangeRate<'info> {
 pub struct TogglePool<'info> {
     #[account(
         mut,
-        seeds = [b"pool", pool.token_mint.as_ref()],
+        seeds = [b"pool", pool.token_mint.as_ref(), pool.quote_mint.as_ref(), &[pool.pool_index]],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, LiquidityPool>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetFee<'info> {
+    #[account(
+        mut,
+        seeds = [b"pool", pool.token_mint.as_ref(), pool.quote_mint.as_ref(), &[pool.pool_index]],
         bump = pool.bump
     )]
     pub pool: Account<'info, LiquidityPool>,
@@ -33,14 +49,124 @@ pub struct TogglePool<'info> {
 pub struct ClosePool<'info> {
     #[account(
         mut,
-        seeds = [b"pool", pool.token_mint.as_ref()],
+        seeds = [b"pool", pool.token_mint.as_ref(), pool.quote_mint.as_ref(), &[pool.pool_index]],
         bump = pool.bump,
         close = authority
     )]
     pub pool: Account<'info, LiquidityPool>,
 
+    #[account(
+        mut,
+        seeds = [b"pool_registry", pool.token_mint.as_ref(), pool.quote_mint.as_ref()],
+        bump = pool_registry.bump
+    )]
+    pub pool_registry: Box<Account<'info, PoolRegistry>>,
+
+    #[account(
+        mut,
+        seeds = [b"mint_registry", pool.token_mint.as_ref()],
+        bump = mint_registry.bump
+    )]
+    pub mint_registry: Box<Account<'info, MintRegistry>>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_vault", pool.key().as_ref()],
+        bump = fee_vault.bump,
+        close = authority
+    )]
+    pub fee_vault: Box<Account<'info, FeeVault>>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault", pool.key().as_ref()],
+        bump = pool.sol_vault_bump
+    )]
+    pub sol_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"oracle", pool.key().as_ref()],
+        bump = oracle.bump,
+        close = authority
+    )]
+    pub oracle: Box<Account<'info, PoolOracle>>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_account", pool.key().as_ref(), authority.key().as_ref()],
+        bump = creator_fee_account.bump,
+        close = authority
+    )]
+    pub creator_fee_account: Box<Account<'info, FeeAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_account", pool.key().as_ref(), b"protocol"],
+        bump = protocol_fee_account.bump,
+        close = authority
+    )]
+    pub protocol_fee_account: Box<Account<'info, FeeAccount>>,
+
+    // Writable so Token-2022 transfer fees withheld in the token vault can be
+    // harvested to it before the vault closes.
+    #[account(
+        mut,
+        address = pool.token_mint @ LiquidityPoolError::InvalidSwapParams
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"pool_token", pool.key().as_ref()],
+        bump
+    )]
+    pub pool_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = authority_token_account.mint == pool.token_mint,
+        constraint = authority_token_account.owner == authority.key()
+    )]
+    pub authority_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"lp_mint", pool.key().as_ref()],
+        bump
+    )]
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [b"lp_lock", pool.key().as_ref()],
+        bump
+    )]
+    pub lp_lock_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = pool.quote_mint @ LiquidityPoolError::UnsupportedQuoteMint)]
+    pub quote_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(
+        mut,
+        seeds = [b"quote_vault", pool.key().as_ref()],
+        bump
+    )]
+    pub quote_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        constraint = authority_quote_account.mint == pool.quote_mint,
+        constraint = authority_quote_account.owner == authority.key()
+    )]
+    pub authority_quote_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
     #[account(mut)]
     pub authority: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
 }
 
 pub fn update_exchange_rate_handler(
@@ -81,17 +207,117 @@ pub fn toggle_pool_handler(ctx: Context<TogglePool>) -> Result<()> {
     Ok(())
 }
 
-pub fn close_pool_handler(ctx: Context<ClosePool>) -> Result<()> {
+pub fn set_fee_handler(ctx: Context<SetFee>, fee_basis_points: u16) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+
+    check_authority(&ctx.accounts.authority.key(), &pool.authority)?;
+    validate_fee(fee_basis_points)?;
+
+    let old_fee_basis_points = pool.fee_basis_points;
+    pool.fee_basis_points = fee_basis_points;
+
+    emit!(FeeUpdated {
+        pool: pool.key(),
+        authority: ctx.accounts.authority.key(),
+        old_fee_basis_points,
+        new_fee_basis_points: fee_basis_points,
+    });
+
+    Ok(())
+}
+
+pub fn close_pool_handler<'info>(ctx: Context<'_, '_, '_, 'info, ClosePool<'info>>) -> Result<()> {
     let pool = &ctx.accounts.pool;
 
     check_authority(&ctx.accounts.authority.key(), &pool.authority)?;
+    // Only the MINIMUM_LIQUIDITY shares locked on the first deposit may be
+    // left. Nobody can redeem them, so the reserves backing them go to the
+    // authority together with any donations.
+    require!(
+        ctx.accounts.lp_mint.supply == ctx.accounts.lp_lock_account.amount,
+        LiquidityPoolError::LiquidityOutstanding
+    );
+    // Fees are only claimable through the pool, so every recipient has to
+    // claim before it goes away.
+    require!(ctx.accounts.fee_vault.unclaimed() == 0, LiquidityPoolError::PoolNotEmpty);
+
+    let seeds = pool.signer_seeds();
+    let token_residue = ctx.accounts.pool_token_account.amount;
+
+    transfer_tokens(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.pool_token_account.to_account_info(),
+                to: ctx.accounts.authority_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                authority: pool.to_account_info(),
+            },
+            &[&seeds]
+        )
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
+        token_residue,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    let quote = QuoteTokenAccounts::load(
+        pool.is_native_quote(),
+        ctx.accounts.quote_mint.as_deref(),
+        ctx.accounts.quote_vault.as_deref(),
+        ctx.accounts.authority_quote_account.as_deref(),
+        ctx.accounts.quote_token_program.as_ref(),
+    )?;
+    if let (Some(quote), Some(quote_vault)) = (quote, ctx.accounts.quote_vault.as_ref()) {
+        quote.transfer_out(pool.to_account_info(), &seeds, quote_vault.amount)?;
+        close_pool_token_vault(&quote.token_program, &quote.vault, &ctx.accounts.authority.to_account_info(), pool)?;
+    }
+
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let authority = ctx.accounts.authority.to_account_info();
+
+    // Token programs only close mints that carry a close authority, so the LP
+    // mint stays behind with zero supply for a pool re-created at this index.
+    burn(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            Burn {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                from: ctx.accounts.lp_lock_account.to_account_info(),
+                authority: pool.to_account_info(),
+            },
+            &[&seeds]
+        ),
+        ctx.accounts.lp_lock_account.amount,
+    )?;
+    close_pool_token_vault(&token_program, &ctx.accounts.lp_lock_account.to_account_info(), &authority, pool)?;
+
+    harvest_withheld_tokens(
+        &token_program,
+        &ctx.accounts.token_mint.to_account_info(),
+        &ctx.accounts.pool_token_account.to_account_info(),
+    )?;
+    close_pool_token_vault(&token_program, &ctx.accounts.pool_token_account.to_account_info(), &authority, pool)?;
+
+    transfer_from_sol_vault(
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.sol_vault.to_account_info(),
+        &ctx.accounts.authority.to_account_info(),
+        &pool.key(),
+        pool.sol_vault_bump,
+        ctx.accounts.sol_vault.lamports(),
+    )?;
+
+    ctx.accounts.pool_registry.remove(&pool.key());
+    if ctx.accounts.pool_registry.pools.is_empty() {
+        ctx.accounts.mint_registry.remove(&pool.quote_mint);
+    }
 
     emit!(PoolClosed {
         pool: pool.key(),
         authority: ctx.accounts.authority.key(),
-        final_sol_reserve: pool.sol_reserve,
-        final_token_reserve: pool.token_reserve,
+        final_sol_reserve: pool.real_sol,
+        final_token_reserve: pool.real_token,
     });
 
     Ok(())
-}
\ No newline at end of file
+}
@@ -0,0 +1,295 @@
+// programs/liquidity_pool/src/instructions/amm_liquidity.rs
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{TokenInterface, TokenAccount, Mint, TransferChecked, MintTo, Burn, mint_to, burn};
+use crate::utils::token::{current_gross_transfer_amount, current_transfer_fee, transfer_tokens, transfer_tokens_to_vault};
+use crate::state::*;
+use crate::error::*;
+use crate::events::*;
+use crate::utils::lp::{calculate_deposit, calculate_initial_lp_amount, calculate_withdrawal};
+use crate::utils::math::{safe_add, safe_sub};
+use crate::utils::vault::{transfer_amm_quote_out, transfer_quote_in, QuoteTokenAccounts};
+
+#[derive(Accounts)]
+pub struct AmmLiquidity<'info> {
+    #[account(
+        mut,
+        seeds = [b"amm_pool", amm_pool.token_mint.as_ref(), amm_pool.quote_mint.as_ref(), &[amm_pool.pool_index]],
+        bump = amm_pool.bump
+    )]
+    pub amm_pool: Box<Account<'info, AmmPool>>,
+
+    #[account(address = amm_pool.token_mint @ LiquidityPoolError::InvalidSwapParams)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [b"amm_token", amm_pool.key().as_ref()],
+        bump = amm_pool.token_vault_bump
+    )]
+    pub amm_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"amm_sol_vault", amm_pool.key().as_ref()],
+        bump = amm_pool.sol_vault_bump
+    )]
+    pub sol_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"amm_lp_mint", amm_pool.key().as_ref()],
+        bump = amm_pool.lp_mint_bump
+    )]
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.mint == amm_pool.token_mint,
+        constraint = user_token_account.owner == user.key()
+    )]
+    pub user_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = amm_pool.quote_mint @ LiquidityPoolError::UnsupportedQuoteMint)]
+    pub quote_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(
+        mut,
+        seeds = [b"amm_quote", amm_pool.key().as_ref()],
+        bump = amm_pool.quote_vault_bump
+    )]
+    pub quote_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        constraint = user_quote_account.mint == amm_pool.quote_mint,
+        constraint = user_quote_account.owner == user.key()
+    )]
+    pub user_quote_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        constraint = user_lp_account.mint == lp_mint.key(),
+        constraint = user_lp_account.owner == user.key()
+    )]
+    pub user_lp_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"amm_lp_lock", amm_pool.key().as_ref()],
+        bump
+    )]
+    pub lp_lock_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn amm_add_liquidity_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, AmmLiquidity<'info>>,
+    max_sol_amount: u64,
+    max_token_amount: u64,
+    min_lp_amount: u64,
+) -> Result<()> {
+    let amm_pool = &mut ctx.accounts.amm_pool;
+    let user = &ctx.accounts.user;
+
+    require!(max_sol_amount > 0 && max_token_amount > 0, LiquidityPoolError::InvalidReserves);
+
+    let mint_info = ctx.accounts.token_mint.to_account_info();
+    let max_token_deposit = safe_sub(max_token_amount, current_transfer_fee(&mint_info, max_token_amount)?)?;
+
+    let lp_supply = ctx.accounts.lp_mint.supply;
+
+    // A deposit into a pool without shares sets the ratio, with
+    // MINIMUM_LIQUIDITY shares locked so the share price cannot be inflated by
+    // donating to an empty pool. Migration seeds its shares the same way.
+    let (lp_amount, sol_amount, token_amount, locked_lp_amount) = if lp_supply > 0 {
+        let (lp_amount, sol_amount, token_amount) = calculate_deposit(
+            max_sol_amount,
+            max_token_deposit,
+            amm_pool.sol_reserve,
+            amm_pool.token_reserve,
+            lp_supply,
+        )?;
+
+        (lp_amount, sol_amount, token_amount, 0)
+    } else {
+        let total_lp_amount = calculate_initial_lp_amount(max_sol_amount, max_token_deposit)?;
+        require!(total_lp_amount > MINIMUM_LIQUIDITY, LiquidityPoolError::InsufficientLiquidity);
+
+        (total_lp_amount - MINIMUM_LIQUIDITY, max_sol_amount, max_token_deposit, MINIMUM_LIQUIDITY)
+    };
+
+    require!(lp_amount > 0, LiquidityPoolError::InvalidSwapAmount);
+    require!(lp_amount >= min_lp_amount, LiquidityPoolError::SlippageExceeded);
+
+    let quote = QuoteTokenAccounts::load(
+        amm_pool.is_native_quote(),
+        ctx.accounts.quote_mint.as_deref(),
+        ctx.accounts.quote_vault.as_deref(),
+        ctx.accounts.user_quote_account.as_deref(),
+        ctx.accounts.quote_token_program.as_ref(),
+    )?;
+    transfer_quote_in(
+        quote.as_ref(),
+        &user.to_account_info(),
+        &ctx.accounts.sol_vault.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        sol_amount,
+    )?;
+
+    let gross_token_amount = current_gross_transfer_amount(&mint_info, token_amount)?;
+    require!(gross_token_amount <= max_token_amount, LiquidityPoolError::SlippageExceeded);
+
+    let received = transfer_tokens_to_vault(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.user_token_account.to_account_info(),
+                to: ctx.accounts.amm_token_vault.to_account_info(),
+                mint: mint_info.clone(),
+                authority: user.to_account_info(),
+            },
+        )
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
+        &mut ctx.accounts.amm_token_vault,
+        gross_token_amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+    require!(received >= token_amount, LiquidityPoolError::VaultTransferShortfall);
+
+    let seeds = amm_pool.signer_seeds();
+
+    if locked_lp_amount > 0 {
+        mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.lp_mint.to_account_info(),
+                    to: ctx.accounts.lp_lock_account.to_account_info(),
+                    authority: amm_pool.to_account_info(),
+                },
+                &[&seeds]
+            ),
+            locked_lp_amount,
+        )?;
+    }
+
+    mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                to: ctx.accounts.user_lp_account.to_account_info(),
+                authority: amm_pool.to_account_info(),
+            },
+            &[&seeds]
+        ),
+        lp_amount,
+    )?;
+
+    amm_pool.sol_reserve = safe_add(amm_pool.sol_reserve, sol_amount)?;
+    amm_pool.token_reserve = safe_add(amm_pool.token_reserve, token_amount)?;
+    amm_pool.locked_lp_amount = safe_add(amm_pool.locked_lp_amount, locked_lp_amount)?;
+
+    emit!(LiquidityAdded {
+        pool: amm_pool.key(),
+        liquidity_provider: user.key(),
+        quote_mint: amm_pool.quote_mint,
+        sol_amount,
+        token_amount,
+        lp_amount,
+        dex_name: DEX_NAME.to_string(),
+        transaction_type: "add_liquidity".to_string(),
+    });
+
+    Ok(())
+}
+
+pub fn amm_remove_liquidity_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, AmmLiquidity<'info>>,
+    lp_amount: u64,
+    min_sol_amount: u64,
+    min_token_amount: u64,
+) -> Result<()> {
+    let amm_pool = &mut ctx.accounts.amm_pool;
+    let user = &ctx.accounts.user;
+
+    let (sol_amount, token_amount) = calculate_withdrawal(
+        lp_amount,
+        ctx.accounts.lp_mint.supply,
+        amm_pool.sol_reserve,
+        amm_pool.token_reserve,
+    )?;
+
+    require!(sol_amount >= min_sol_amount, LiquidityPoolError::SlippageExceeded);
+    require!(token_amount >= min_token_amount, LiquidityPoolError::SlippageExceeded);
+
+    burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                from: ctx.accounts.user_lp_account.to_account_info(),
+                authority: user.to_account_info(),
+            },
+        ),
+        lp_amount,
+    )?;
+
+    if token_amount > 0 {
+        let seeds = amm_pool.signer_seeds();
+
+        transfer_tokens(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.amm_token_vault.to_account_info(),
+                    to: ctx.accounts.user_token_account.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    authority: amm_pool.to_account_info(),
+                },
+                &[&seeds]
+            )
+            .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
+            token_amount,
+            ctx.accounts.token_mint.decimals,
+        )?;
+    }
+
+    let quote = QuoteTokenAccounts::load(
+        amm_pool.is_native_quote(),
+        ctx.accounts.quote_mint.as_deref(),
+        ctx.accounts.quote_vault.as_deref(),
+        ctx.accounts.user_quote_account.as_deref(),
+        ctx.accounts.quote_token_program.as_ref(),
+    )?;
+    transfer_amm_quote_out(
+        quote.as_ref(),
+        amm_pool,
+        &ctx.accounts.sol_vault.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &user.to_account_info(),
+        sol_amount,
+    )?;
+
+    amm_pool.sol_reserve = safe_sub(amm_pool.sol_reserve, sol_amount)?;
+    amm_pool.token_reserve = safe_sub(amm_pool.token_reserve, token_amount)?;
+
+    emit!(LiquidityRemoved {
+        pool: amm_pool.key(),
+        liquidity_provider: user.key(),
+        quote_mint: amm_pool.quote_mint,
+        sol_amount,
+        token_amount,
+        lp_amount,
+        dex_name: DEX_NAME.to_string(),
+        transaction_type: "remove_liquidity".to_string(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,259 @@
+// programs/liquidity_pool/src/instructions/amm_swap.rs
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{TokenInterface, TokenAccount, Mint, TransferChecked};
+use crate::utils::quote::{quote_amm_swap, AmmQuote};
+use crate::utils::token::{current_gross_transfer_amount, transfer_tokens, transfer_tokens_to_vault};
+use crate::state::*;
+use crate::error::*;
+use crate::events::*;
+use crate::utils::math::*;
+use crate::utils::vault::{transfer_amm_quote_out, transfer_quote_in, QuoteTokenAccounts};
+
+#[derive(Accounts)]
+pub struct AmmSwap<'info> {
+    #[account(
+        mut,
+        seeds = [b"amm_pool", amm_pool.token_mint.as_ref(), amm_pool.quote_mint.as_ref(), &[amm_pool.pool_index]],
+        bump = amm_pool.bump
+    )]
+    pub amm_pool: Box<Account<'info, AmmPool>>,
+
+    #[account(address = amm_pool.token_mint @ LiquidityPoolError::InvalidSwapParams)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [b"amm_token", amm_pool.key().as_ref()],
+        bump = amm_pool.token_vault_bump
+    )]
+    pub amm_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"amm_sol_vault", amm_pool.key().as_ref()],
+        bump = amm_pool.sol_vault_bump
+    )]
+    pub sol_vault: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump
+    )]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.mint == amm_pool.token_mint,
+        constraint = user_token_account.owner == user.key()
+    )]
+    pub user_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = amm_pool.quote_mint @ LiquidityPoolError::UnsupportedQuoteMint)]
+    pub quote_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(
+        mut,
+        seeds = [b"amm_quote", amm_pool.key().as_ref()],
+        bump = amm_pool.quote_vault_bump
+    )]
+    pub quote_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        constraint = user_quote_account.mint == amm_pool.quote_mint,
+        constraint = user_quote_account.owner == user.key()
+    )]
+    pub user_quote_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Prices the swap against the AMM pool. Sold tokens are pulled into the
+/// vault first so the swap is priced on what actually arrived, net of any
+/// transfer fee; `max_amount_in` bounds what the user sends.
+fn price_amm_swap<'info>(
+    accounts: &mut AmmSwap<'info>,
+    remaining_accounts: &[AccountInfo<'info>],
+    direction: SwapDirection,
+    mode: SwapMode,
+) -> Result<AmmQuote> {
+    if direction == SwapDirection::Buy {
+        return quote_amm_swap(&accounts.amm_pool, direction, mode);
+    }
+
+    match mode {
+        SwapMode::ExactIn { amount_in, min_amount_out } => {
+            let received = transfer_sold_tokens(accounts, remaining_accounts, amount_in)?;
+
+            quote_amm_swap(
+                &accounts.amm_pool,
+                direction,
+                SwapMode::ExactIn { amount_in: received, min_amount_out },
+            )
+        },
+        SwapMode::ExactOut { amount_out, max_amount_in } => {
+            let quote = quote_amm_swap(
+                &accounts.amm_pool,
+                direction,
+                SwapMode::ExactOut { amount_out, max_amount_in: u64::MAX },
+            )?;
+            let gross_token_amount = current_gross_transfer_amount(&accounts.token_mint.to_account_info(), quote.amount_in)?;
+            require!(gross_token_amount <= max_amount_in, LiquidityPoolError::SlippageExceeded);
+
+            let received = transfer_sold_tokens(accounts, remaining_accounts, gross_token_amount)?;
+            require!(received >= quote.amount_in, LiquidityPoolError::VaultTransferShortfall);
+
+            Ok(quote)
+        },
+    }
+}
+
+fn transfer_sold_tokens<'info>(
+    accounts: &mut AmmSwap<'info>,
+    remaining_accounts: &[AccountInfo<'info>],
+    amount: u64,
+) -> Result<u64> {
+    transfer_tokens_to_vault(
+        CpiContext::new(
+            accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: accounts.user_token_account.to_account_info(),
+                to: accounts.amm_token_vault.to_account_info(),
+                mint: accounts.token_mint.to_account_info(),
+                authority: accounts.user.to_account_info(),
+            },
+        )
+        .with_remaining_accounts(remaining_accounts.to_vec()),
+        &mut accounts.amm_token_vault,
+        amount,
+        accounts.token_mint.decimals,
+    )
+}
+
+pub fn amm_swap_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, AmmSwap<'info>>,
+    direction: SwapDirection,
+    mode: SwapMode,
+) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+
+    require!(!ctx.accounts.global_config.is_paused, LiquidityPoolError::ProtocolPaused);
+
+    let AmmQuote { amount_in, amount_out, fee_amount, spot_price } =
+        price_amm_swap(ctx.accounts, ctx.remaining_accounts, direction, mode)?;
+    let amm_pool = &mut ctx.accounts.amm_pool;
+
+    let (token_amount, sol_amount) = match direction {
+        SwapDirection::Buy => (amount_out, amount_in),
+        SwapDirection::Sell => (amount_in, amount_out),
+    };
+
+    // Sells pay the fee in tokens; it is reported in quote units at the
+    // pre-swap price, like the launch pool's fees.
+    let fee_amount = match direction {
+        SwapDirection::Buy => fee_amount,
+        SwapDirection::Sell => u128_to_u64(mul_div_u128(
+            fee_amount as u128,
+            spot_price,
+            NORMALIZED_UNITS_PER_TOKEN * PRICE_SCALE,
+            false,
+        )?)?,
+    };
+
+    let expected_sol_amount = u128_to_u64(mul_div_u128(
+        token_amount as u128,
+        spot_price,
+        NORMALIZED_UNITS_PER_TOKEN * PRICE_SCALE,
+        false,
+    )?)?;
+    let seeds = amm_pool.signer_seeds();
+    let quote = QuoteTokenAccounts::load(
+        amm_pool.is_native_quote(),
+        ctx.accounts.quote_mint.as_deref(),
+        ctx.accounts.quote_vault.as_deref(),
+        ctx.accounts.user_quote_account.as_deref(),
+        ctx.accounts.quote_token_program.as_ref(),
+    )?;
+
+    match direction {
+        SwapDirection::Buy => {
+            require!(token_amount < amm_pool.token_reserve, LiquidityPoolError::InsufficientLiquidity);
+
+            transfer_quote_in(
+                quote.as_ref(),
+                &ctx.accounts.user.to_account_info(),
+                &ctx.accounts.sol_vault.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                sol_amount,
+            )?;
+
+            transfer_tokens(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: ctx.accounts.amm_token_vault.to_account_info(),
+                        to: ctx.accounts.user_token_account.to_account_info(),
+                        mint: ctx.accounts.token_mint.to_account_info(),
+                        authority: amm_pool.to_account_info(),
+                    },
+                    &[&seeds]
+                )
+                .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
+                token_amount,
+                ctx.accounts.token_mint.decimals,
+            )?;
+
+            amm_pool.sol_reserve = safe_add(amm_pool.sol_reserve, sol_amount)?;
+            amm_pool.token_reserve = safe_sub(amm_pool.token_reserve, token_amount)?;
+        },
+        SwapDirection::Sell => {
+            require!(sol_amount < amm_pool.sol_reserve, LiquidityPoolError::InsufficientLiquidity);
+
+            transfer_amm_quote_out(
+                quote.as_ref(),
+                amm_pool,
+                &ctx.accounts.sol_vault.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                &ctx.accounts.user.to_account_info(),
+                sol_amount,
+            )?;
+
+            amm_pool.sol_reserve = safe_sub(amm_pool.sol_reserve, sol_amount)?;
+            amm_pool.token_reserve = safe_add(amm_pool.token_reserve, token_amount)?;
+        },
+    }
+
+    amm_pool.update_trade_stats(sol_amount, token_amount, current_time);
+
+    emit!(SwapExecuted {
+        pool: amm_pool.key(),
+        trader: ctx.accounts.user.key(),
+        token_mint: amm_pool.token_mint,
+        quote_mint: amm_pool.quote_mint,
+        dex_name: DEX_NAME.to_string(),
+        trade_direction: match direction {
+            SwapDirection::Buy => "buy".to_string(),
+            SwapDirection::Sell => "sell".to_string(),
+        },
+        token_amount,
+        sol_amount,
+        exchange_rate: Price::from_amounts(
+            sol_amount,
+            normalize_token_amount(token_amount, ctx.accounts.token_mint.decimals)?,
+        )?,
+        timestamp: current_time,
+        transaction_type: TRANSACTION_TYPE.to_string(),
+        pool_type: AMM_POOL_TYPE.to_string(),
+        slippage_bps: calculate_slippage(expected_sol_amount, sol_amount),
+        fee_amount,
+        program_version: PROGRAM_VERSION.to_string(),
+        network: get_network_name(),
+    });
+
+    Ok(())
+}
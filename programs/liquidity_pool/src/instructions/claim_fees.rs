@@ -0,0 +1,184 @@
+// programs/liquidity_pool/src/instructions/claim_fees.rs
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::error::*;
+use crate::events::*;
+use crate::utils::math::safe_add;
+use crate::utils::vault::QuoteTokenAccounts;
+
+#[derive(Accounts)]
+pub struct ClaimFees<'info> {
+    #[account(
+        seeds = [b"pool", pool.token_mint.as_ref(), pool.quote_mint.as_ref(), &[pool.pool_index]],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, LiquidityPool>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_vault", pool.key().as_ref()],
+        bump = fee_vault.bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_account", pool.key().as_ref(), recipient.key().as_ref()],
+        bump = fee_account.bump
+    )]
+    pub fee_account: Account<'info, FeeAccount>,
+
+    #[account(mut)]
+    pub recipient: Signer<'info>,
+
+    #[account(address = pool.quote_mint @ LiquidityPoolError::UnsupportedQuoteMint)]
+    pub quote_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(
+        mut,
+        seeds = [b"quote_vault", pool.key().as_ref()],
+        bump
+    )]
+    pub quote_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        constraint = recipient_quote_account.mint == pool.quote_mint,
+        constraint = recipient_quote_account.owner == recipient.key()
+    )]
+    pub recipient_quote_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub quote_token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimProtocolFees<'info> {
+    #[account(
+        seeds = [b"pool", pool.token_mint.as_ref(), pool.quote_mint.as_ref(), &[pool.pool_index]],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, LiquidityPool>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_vault", pool.key().as_ref()],
+        bump = fee_vault.bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump
+    )]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_account", pool.key().as_ref(), b"protocol"],
+        bump = protocol_fee_account.bump
+    )]
+    pub protocol_fee_account: Account<'info, FeeAccount>,
+
+    /// CHECK: Only receives the payout, and must be the current treasury.
+    #[account(
+        mut,
+        address = global_config.treasury @ LiquidityPoolError::UnauthorizedAccess
+    )]
+    pub treasury: UncheckedAccount<'info>,
+
+    #[account(address = pool.quote_mint @ LiquidityPoolError::UnsupportedQuoteMint)]
+    pub quote_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(
+        mut,
+        seeds = [b"quote_vault", pool.key().as_ref()],
+        bump
+    )]
+    pub quote_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        constraint = treasury_quote_account.mint == pool.quote_mint,
+        constraint = treasury_quote_account.owner == treasury.key()
+    )]
+    pub treasury_quote_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub quote_token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+pub fn claim_fees_handler(ctx: Context<ClaimFees>) -> Result<()> {
+    // Fees of pools quoted in an SPL token are held in the quote vault.
+    let quote = QuoteTokenAccounts::load(
+        ctx.accounts.pool.is_native_quote(),
+        ctx.accounts.quote_mint.as_deref(),
+        ctx.accounts.quote_vault.as_deref(),
+        ctx.accounts.recipient_quote_account.as_deref(),
+        ctx.accounts.quote_token_program.as_ref(),
+    )?;
+
+    pay_fees(
+        &ctx.accounts.pool,
+        &mut ctx.accounts.fee_vault,
+        &mut ctx.accounts.fee_account,
+        &ctx.accounts.recipient.to_account_info(),
+        quote,
+    )
+}
+
+/// Pays the protocol's share of a pool's fees to the current treasury.
+/// Anyone may crank it, since the payout can only go to the treasury.
+pub fn claim_protocol_fees_handler(ctx: Context<ClaimProtocolFees>) -> Result<()> {
+    let quote = QuoteTokenAccounts::load(
+        ctx.accounts.pool.is_native_quote(),
+        ctx.accounts.quote_mint.as_deref(),
+        ctx.accounts.quote_vault.as_deref(),
+        ctx.accounts.treasury_quote_account.as_deref(),
+        ctx.accounts.quote_token_program.as_ref(),
+    )?;
+
+    pay_fees(
+        &ctx.accounts.pool,
+        &mut ctx.accounts.fee_vault,
+        &mut ctx.accounts.protocol_fee_account,
+        &ctx.accounts.treasury.to_account_info(),
+        quote,
+    )
+}
+
+fn pay_fees<'info>(
+    pool: &Account<'info, LiquidityPool>,
+    fee_vault: &mut Account<'info, FeeVault>,
+    fee_account: &mut Account<'info, FeeAccount>,
+    recipient: &AccountInfo<'info>,
+    quote: Option<QuoteTokenAccounts<'info>>,
+) -> Result<()> {
+    let amount = fee_account.claimable();
+    require!(amount > 0, LiquidityPoolError::NoFeesToClaim);
+
+    match quote {
+        Some(quote) => quote.transfer_out(pool.to_account_info(), &pool.signer_seeds(), amount)?,
+        None => {
+            let rent_exempt_minimum = Rent::get()?.minimum_balance(FeeVault::SPACE);
+            require!(
+                fee_vault.to_account_info().lamports() >= safe_add(rent_exempt_minimum, amount)?,
+                LiquidityPoolError::InsufficientLiquidity
+            );
+
+            **fee_vault.to_account_info().try_borrow_mut_lamports()? -= amount;
+            **recipient.try_borrow_mut_lamports()? += amount;
+        },
+    }
+
+    fee_account.total_claimed = safe_add(fee_account.total_claimed, amount)?;
+    fee_vault.total_claimed = safe_add(fee_vault.total_claimed, amount)?;
+
+    emit!(FeesClaimed {
+        pool: pool.key(),
+        recipient: recipient.key(),
+        amount,
+        total_claimed: fee_account.total_claimed,
+    });
+
+    Ok(())
+}
@@ -1,30 +1,115 @@
 // programs/liquidity_pool/src/instructions/create_pool.rs
 use anchor_lang::prelude::*;
-use anchor_spl::token::Mint;
-use crate::state::{LiquidityPool, DEX_NAME, POOL_TYPE, PROGRAM_VERSION};
+use anchor_spl::token_interface::Mint;
+use crate::state::{MINT_REGISTRY_SEED, 
+    LiquidityPool, FeeVault, GlobalConfig, FeeAccount, PoolOracle, PoolRegistry, RegisteredPool, MintRegistry, CreatorAllowlistEntry,
+    PoolCreationPolicy, CompletionThreshold, CurveKind, PoolStatus, DEX_NAME, NATIVE_QUOTE_MINT,
+    POOL_TYPE, PROGRAM_VERSION,
+};
 use crate::events::*;
 use crate::error::LiquidityPoolError;
+use crate::utils::curve::{Curve, PoolCurve};
+use crate::utils::math::{price_to_exchange_rate, Price};
+use crate::utils::vault::sol_vault_rent_exempt_minimum;
+use crate::utils::token::{validate_mint_extensions, validate_quote_mint_extensions};
+use crate::utils::validation::{validate_completion_threshold, validate_curve};
 
 #[derive(Accounts)]
+#[instruction(pool_index: u8)]
 pub struct CreatePool<'info> {
+    // Initialized ahead of `pool`, so an existing pool is reported as
+    // `PoolAlreadyExists` rather than failing the pool's account creation.
+    #[account(
+        init_if_needed,
+        payer = pool_creator,
+        space = PoolRegistry::SPACE,
+        seeds = [b"pool_registry", token_mint.key().as_ref(), quote_mint.key().as_ref()],
+        bump,
+        constraint = !pool_registry.contains(&pool.key()) @ LiquidityPoolError::PoolAlreadyExists
+    )]
+    pub pool_registry: Box<Account<'info, PoolRegistry>>,
+
+    /// CHECK: The token's `MintRegistry`. It grows with every new quote
+    /// mint, which `init_if_needed` cannot express, so the handler creates
+    /// and resizes it.
+    #[account(
+        mut,
+        seeds = [b"mint_registry", token_mint.key().as_ref()],
+        bump
+    )]
+    pub mint_registry: UncheckedAccount<'info>,
+
     #[account(
         init,
         payer = pool_creator,
         space = LiquidityPool::SPACE,
-        seeds = [b"pool", token_mint.key().as_ref()],
+        seeds = [b"pool", token_mint.key().as_ref(), quote_mint.key().as_ref(), &[pool_index]],
         bump
     )]
     pub pool: Account<'info, LiquidityPool>,
 
     #[account(
-        constraint = program_authority.key() == crate::ID @ LiquidityPoolError::UnauthorizedAccess
+        init,
+        payer = pool_creator,
+        space = FeeVault::SPACE,
+        seeds = [b"fee_vault", pool.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: Box<Account<'info, FeeVault>>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault", pool.key().as_ref()],
+        bump
+    )]
+    pub sol_vault: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump
+    )]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+
+    #[account(
+        seeds = [b"creator_allowlist", pool_creator.key().as_ref()],
+        bump = creator_allowlist_entry.bump
+    )]
+    pub creator_allowlist_entry: Option<Account<'info, CreatorAllowlistEntry>>,
+
+    #[account(
+        init,
+        payer = pool_creator,
+        space = FeeAccount::SPACE,
+        seeds = [b"fee_account", pool.key().as_ref(), pool_creator.key().as_ref()],
+        bump
+    )]
+    pub creator_fee_account: Box<Account<'info, FeeAccount>>,
+
+    #[account(
+        init,
+        payer = pool_creator,
+        space = FeeAccount::SPACE,
+        seeds = [b"fee_account", pool.key().as_ref(), b"protocol"],
+        bump
     )]
-    pub program_authority: Signer<'info>,
+    pub protocol_fee_account: Box<Account<'info, FeeAccount>>,
+
+    #[account(
+        init,
+        payer = pool_creator,
+        space = PoolOracle::SPACE,
+        seeds = [b"oracle", pool.key().as_ref()],
+        bump
+    )]
+    pub oracle: Box<Account<'info, PoolOracle>>,
 
     #[account(mut)]
     pub pool_creator: Signer<'info>,
 
-    pub token_mint: Account<'info, Mint>,
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// Asset the pool is priced in. The native mint makes a SOL pool.
+    pub quote_mint: InterfaceAccount<'info, Mint>,
 
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
@@ -32,9 +117,30 @@ pub struct CreatePool<'info> {
 
 pub fn handler(
     ctx: Context<CreatePool>,
+    pool_index: u8,
     virtual_token_reserve: Option<u64>,
     virtual_sol_reserve: Option<u64>,
+    curve: CurveKind,
+    completion_threshold: CompletionThreshold,
 ) -> Result<()> {
+    let global_config = &ctx.accounts.global_config;
+
+    require!(!global_config.is_paused, LiquidityPoolError::ProtocolPaused);
+    validate_curve(&curve)?;
+    validate_completion_threshold(&completion_threshold)?;
+    validate_mint_extensions(&ctx.accounts.token_mint.to_account_info())?;
+    validate_quote_mint_extensions(&ctx.accounts.quote_mint.to_account_info())?;
+    require!(
+        ctx.accounts.quote_mint.key() != ctx.accounts.token_mint.key(),
+        LiquidityPoolError::UnsupportedQuoteMint
+    );
+    if global_config.pool_creation_policy == PoolCreationPolicy::Allowlisted {
+        require!(
+            ctx.accounts.creator_allowlist_entry.is_some(),
+            LiquidityPoolError::CreatorNotAllowed
+        );
+    }
+
     let pool = &mut ctx.accounts.pool;
     let pool_creator = &ctx.accounts.pool_creator;
     let current_time = Clock::get()?.unix_timestamp;
@@ -42,39 +148,214 @@ pub fn handler(
     const DEFAULT_VIRTUAL_SOL: u64 = 30_000_000_000;
     const DEFAULT_VIRTUAL_TOKEN: u64 = 1_073_000_000_000_000;
 
-    let final_virtual_sol = virtual_sol_reserve.unwrap_or(DEFAULT_VIRTUAL_SOL);
-    let final_virtual_token = virtual_token_reserve.unwrap_or(DEFAULT_VIRTUAL_TOKEN);
+    let native_quote = ctx.accounts.quote_mint.key() == NATIVE_QUOTE_MINT;
+
+    // The default virtual reserve is sized in lamports, so pools quoted in
+    // another mint must pick their own.
+    require!(
+        native_quote || curve != CurveKind::ConstantProduct || virtual_sol_reserve.is_some(),
+        LiquidityPoolError::InvalidReserves
+    );
+
+    let (final_virtual_sol, final_virtual_token) = match curve {
+        CurveKind::ConstantProduct => (
+            virtual_sol_reserve.unwrap_or(DEFAULT_VIRTUAL_SOL),
+            virtual_token_reserve.unwrap_or(DEFAULT_VIRTUAL_TOKEN),
+        ),
+        _ => (0, 0),
+    };
+    let spot_price = PoolCurve::new(curve, 0, final_virtual_sol, final_virtual_token).spot_price()?;
 
     pool.authority = pool_creator.key();
     pool.token_mint = ctx.accounts.token_mint.key();
-    pool.sol_reserve = final_virtual_sol;
-    pool.token_reserve = final_virtual_token;
-    pool.exchange_rate = final_virtual_token
-        .checked_mul(1_000_000)
-        .unwrap_or(u64::MAX)
-        .checked_div(final_virtual_sol)
-        .unwrap_or(1_000_000_000_000);
+    pool.quote_mint = ctx.accounts.quote_mint.key();
+    pool.pool_index = pool_index;
+    pool.virtual_sol = final_virtual_sol;
+    pool.virtual_token = final_virtual_token;
+    pool.real_sol = 0;
+    pool.real_token = 0;
+    pool.exchange_rate = price_to_exchange_rate(spot_price)?;
     pool.is_active = true;
     pool.created_at = current_time;
-    pool.fee_basis_points = 0;
+    pool.fee_basis_points = global_config.default_fee_basis_points;
     pool.bump = ctx.bumps.pool;
     pool.total_volume_sol = 0;
     pool.total_volume_token = 0;
     pool.trade_count = 0;
     pool.last_trade_timestamp = 0;
-    pool.current_price = (final_virtual_sol as f64) / (final_virtual_token as f64);
+    pool.current_price = Price::new(spot_price);
+    pool.curve = curve;
+    pool.tokens_sold = 0;
+    pool.status = PoolStatus::Trading;
+    pool.completion_threshold = completion_threshold;
+    pool.sol_vault_bump = ctx.bumps.sol_vault;
+    pool.price_cumulative = 0;
+    pool.price_last_updated = current_time;
+
+    // Fund the vault's rent-exempt minimum up front so reserve transfers of any
+    // size into it succeed. Pools quoted in another mint keep their reserve in
+    // the quote vault instead.
+    let sol_vault_rent = sol_vault_rent_exempt_minimum()?.saturating_sub(ctx.accounts.sol_vault.lamports());
+    if native_quote && sol_vault_rent > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: pool_creator.to_account_info(),
+                    to: ctx.accounts.sol_vault.to_account_info(),
+                },
+            ),
+            sol_vault_rent,
+        )?;
+    }
+
+    let pool_registry = &mut ctx.accounts.pool_registry;
+    pool_registry.token_mint = ctx.accounts.token_mint.key();
+    pool_registry.quote_mint = ctx.accounts.quote_mint.key();
+    pool_registry.bump = ctx.bumps.pool_registry;
+    pool_registry.pools.push(RegisteredPool {
+        pool: pool.key(),
+        pool_index,
+    });
+
+    register_quote_mint(
+        &ctx.accounts.mint_registry,
+        ctx.bumps.mint_registry,
+        &pool.token_mint,
+        &pool.quote_mint,
+        &pool_creator.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    let fee_vault = &mut ctx.accounts.fee_vault;
+    fee_vault.pool = pool.key();
+    fee_vault.bump = ctx.bumps.fee_vault;
+    fee_vault.total_collected = 0;
+    fee_vault.total_claimed = 0;
+
+    let creator_fee_account = &mut ctx.accounts.creator_fee_account;
+    creator_fee_account.pool = pool.key();
+    creator_fee_account.recipient = pool_creator.key();
+    creator_fee_account.bump = ctx.bumps.creator_fee_account;
+
+    let protocol_fee_account = &mut ctx.accounts.protocol_fee_account;
+    protocol_fee_account.pool = pool.key();
+    // Paid out to whoever is the treasury when it is claimed.
+    protocol_fee_account.recipient = Pubkey::default();
+    protocol_fee_account.bump = ctx.bumps.protocol_fee_account;
+
+    let oracle = &mut ctx.accounts.oracle;
+    oracle.pool = pool.key();
+    oracle.bump = ctx.bumps.oracle;
+    oracle.index = 0;
+    oracle.observations = Vec::new();
+    oracle.record(current_time, 0);
 
     emit!(PoolCreated {
         pool: pool.key(),
         authority: pool_creator.key(),
         token_mint: ctx.accounts.token_mint.key(),
+        quote_mint: ctx.accounts.quote_mint.key(),
+        pool_index,
         initial_exchange_rate: pool.exchange_rate,
         initial_sol: final_virtual_sol,
         initial_tokens: final_virtual_token,
+        curve,
+        completion_threshold,
         dex_name: DEX_NAME.to_string(),
         pool_type: POOL_TYPE.to_string(),
         version: PROGRAM_VERSION.to_string(),
     });
 
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Adds `quote_mint` to the token's `MintRegistry`, creating the registry on
+/// the token's first pool.
+fn register_quote_mint<'info>(
+    mint_registry: &AccountInfo<'info>,
+    bump: u8,
+    token_mint: &Pubkey,
+    quote_mint: &Pubkey,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let mut registry = if *mint_registry.owner == crate::ID {
+        MintRegistry::try_deserialize(&mut &mint_registry.try_borrow_data()?[..])?
+    } else {
+        MintRegistry {
+            token_mint: *token_mint,
+            bump,
+            quote_mints: Vec::new(),
+        }
+    };
+    if registry.contains(quote_mint) {
+        return Ok(());
+    }
+    registry.quote_mints.push(*quote_mint);
+
+    resize_registry(
+        mint_registry,
+        MintRegistry::space(registry.quote_mints.len()),
+        payer,
+        system_program,
+        &[MINT_REGISTRY_SEED, token_mint.as_ref(), &[bump]],
+    )?;
+
+    let mut data = mint_registry.try_borrow_mut_data()?;
+    registry.try_serialize(&mut &mut data[..])
+}
+
+/// Grows `account` to at least `space` bytes with `payer` topping up its rent,
+/// allocating it to this program first if it does not exist yet. Entries
+/// removed by `close_pool` leave their space behind for reuse.
+fn resize_registry<'info>(
+    account: &AccountInfo<'info>,
+    space: usize,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    signer_seeds: &[&[u8]],
+) -> Result<()> {
+    if account.data_len() >= space {
+        return Ok(());
+    }
+
+    let rent_shortfall = Rent::get()?.minimum_balance(space).saturating_sub(account.lamports());
+    if rent_shortfall > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                anchor_lang::system_program::Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            rent_shortfall,
+        )?;
+    }
+
+    if *account.owner != crate::ID {
+        anchor_lang::system_program::allocate(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                anchor_lang::system_program::Allocate {
+                    account_to_allocate: account.clone(),
+                },
+                &[signer_seeds]
+            ),
+            space as u64,
+        )?;
+        return anchor_lang::system_program::assign(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                anchor_lang::system_program::Assign {
+                    account_to_assign: account.clone(),
+                },
+                &[signer_seeds]
+            ),
+            &crate::ID,
+        );
+    }
+
+    account.realloc(space, false).map_err(Into::into)
+}
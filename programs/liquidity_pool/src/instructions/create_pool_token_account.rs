@@ -1,12 +1,13 @@
 // programs/liquidity_pool/src/instructions/create_pool_token_account.rs
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount, Mint};
+use anchor_spl::token_interface::{TokenInterface, TokenAccount, Mint};
 use crate::state::*;
+use crate::error::LiquidityPoolError;
 
 #[derive(Accounts)]
 pub struct CreatePoolTokenAccount<'info> {
     #[account(
-        seeds = [b"pool", pool.token_mint.as_ref()],
+        seeds = [b"pool", pool.token_mint.as_ref(), pool.quote_mint.as_ref(), &[pool.pool_index]],
         bump = pool.bump
     )]
     pub pool: Account<'info, LiquidityPool>,
@@ -14,23 +15,68 @@ pub struct CreatePoolTokenAccount<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
 
-    pub token_mint: Account<'info, Mint>,
+    #[account(address = pool.token_mint @ LiquidityPoolError::InvalidSwapParams)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         init,
         payer = payer,
-        seeds = [b"pool_token", token_mint.key().as_ref()],
+        seeds = [b"pool_token", pool.key().as_ref()],
         bump,
         token::mint = token_mint,
         token::authority = pool
     )]
-    pub pool_token_account: Account<'info, TokenAccount>,
+    pub pool_token_account: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_program: Program<'info, Token>,
+    // Outlives `close_pool`, which burns its supply to zero, so a pool
+    // re-created at the same index takes over the existing mint.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [b"lp_mint", pool.key().as_ref()],
+        bump,
+        mint::decimals = LP_MINT_DECIMALS,
+        mint::authority = pool
+    )]
+    pub lp_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"lp_lock", pool.key().as_ref()],
+        bump,
+        token::mint = lp_mint,
+        token::authority = pool
+    )]
+    pub lp_lock_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = pool.quote_mint @ LiquidityPoolError::UnsupportedQuoteMint)]
+    pub quote_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"quote_vault", pool.key().as_ref()],
+        bump,
+        token::mint = quote_mint,
+        token::authority = pool,
+        token::token_program = quote_token_program
+    )]
+    pub quote_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Option<Interface<'info, TokenInterface>>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
 
-pub fn create_pool_token_account_handler(_ctx: Context<CreatePoolTokenAccount>) -> Result<()> {
+pub fn create_pool_token_account_handler(ctx: Context<CreatePoolTokenAccount>) -> Result<()> {
+    // Pools quoted in an SPL token hold their quote reserve in a vault created
+    // alongside the token vault.
+    require!(
+        ctx.accounts.pool.is_native_quote() || ctx.accounts.quote_vault.is_some(),
+        LiquidityPoolError::MissingQuoteAccounts
+    );
+
     Ok(())
-}
\ No newline at end of file
+}
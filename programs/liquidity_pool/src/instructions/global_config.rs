@@ -0,0 +1,176 @@
+// programs/liquidity_pool/src/instructions/global_config.rs
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::*;
+use crate::events::*;
+use crate::utils::validation::{validate_fee, validate_fee_shares};
+
+#[derive(Accounts)]
+pub struct InitializeGlobalConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = GlobalConfig::SPACE,
+        seeds = [b"global_config"],
+        bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key()) @ LiquidityPoolError::UnauthorizedAccess
+    )]
+    pub program: Program<'info, crate::program::LiquidityPool>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ LiquidityPoolError::UnauthorizedAccess
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateGlobalConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_config"],
+        bump = global_config.bump,
+        has_one = admin @ LiquidityPoolError::UnauthorizedAccess
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(creator: Pubkey)]
+pub struct AddAllowedCreator<'info> {
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump,
+        has_one = admin @ LiquidityPoolError::UnauthorizedAccess
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = CreatorAllowlistEntry::SPACE,
+        seeds = [b"creator_allowlist", creator.as_ref()],
+        bump
+    )]
+    pub allowlist_entry: Account<'info, CreatorAllowlistEntry>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveAllowedCreator<'info> {
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump,
+        has_one = admin @ LiquidityPoolError::UnauthorizedAccess
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"creator_allowlist", allowlist_entry.creator.as_ref()],
+        bump = allowlist_entry.bump,
+        close = admin
+    )]
+    pub allowlist_entry: Account<'info, CreatorAllowlistEntry>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+fn validate_global_config_params(params: &GlobalConfigParams) -> Result<()> {
+    validate_fee_shares(params.protocol_share_bps, params.creator_share_bps, params.referrer_share_bps)?;
+    validate_fee(params.default_fee_basis_points)
+}
+
+fn emit_global_config_updated(global_config: &GlobalConfig) {
+    emit!(GlobalConfigUpdated {
+        admin: global_config.admin,
+        treasury: global_config.treasury,
+        protocol_share_bps: global_config.protocol_share_bps,
+        creator_share_bps: global_config.creator_share_bps,
+        referrer_share_bps: global_config.referrer_share_bps,
+        default_fee_basis_points: global_config.default_fee_basis_points,
+        pool_creation_policy: global_config.pool_creation_policy,
+        is_paused: global_config.is_paused,
+    });
+}
+
+pub fn initialize_global_config_handler(
+    ctx: Context<InitializeGlobalConfig>,
+    params: GlobalConfigParams,
+) -> Result<()> {
+    validate_global_config_params(&params)?;
+
+    let global_config = &mut ctx.accounts.global_config;
+    global_config.admin = ctx.accounts.admin.key();
+    global_config.apply(&params);
+    global_config.is_paused = false;
+    global_config.bump = ctx.bumps.global_config;
+
+    emit_global_config_updated(global_config);
+
+    Ok(())
+}
+
+pub fn update_global_config_handler(
+    ctx: Context<UpdateGlobalConfig>,
+    new_admin: Option<Pubkey>,
+    params: GlobalConfigParams,
+) -> Result<()> {
+    validate_global_config_params(&params)?;
+
+    let global_config = &mut ctx.accounts.global_config;
+    if let Some(new_admin) = new_admin {
+        global_config.admin = new_admin;
+    }
+    global_config.apply(&params);
+
+    emit_global_config_updated(global_config);
+
+    Ok(())
+}
+
+pub fn set_paused_handler(ctx: Context<UpdateGlobalConfig>, paused: bool) -> Result<()> {
+    let global_config = &mut ctx.accounts.global_config;
+    global_config.is_paused = paused;
+
+    emit_global_config_updated(global_config);
+
+    Ok(())
+}
+
+pub fn add_allowed_creator_handler(ctx: Context<AddAllowedCreator>, creator: Pubkey) -> Result<()> {
+    let allowlist_entry = &mut ctx.accounts.allowlist_entry;
+    allowlist_entry.creator = creator;
+    allowlist_entry.bump = ctx.bumps.allowlist_entry;
+
+    emit!(CreatorAllowlistUpdated {
+        creator,
+        allowed: true,
+    });
+
+    Ok(())
+}
+
+pub fn remove_allowed_creator_handler(ctx: Context<RemoveAllowedCreator>) -> Result<()> {
+    emit!(CreatorAllowlistUpdated {
+        creator: ctx.accounts.allowlist_entry.creator,
+        allowed: false,
+    });
+
+    Ok(())
+}
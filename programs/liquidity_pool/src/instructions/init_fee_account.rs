@@ -0,0 +1,38 @@
+// programs/liquidity_pool/src/instructions/init_fee_account.rs
+use anchor_lang::prelude::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(recipient: Pubkey)]
+pub struct InitFeeAccount<'info> {
+    #[account(
+        seeds = [b"pool", pool.token_mint.as_ref(), pool.quote_mint.as_ref(), &[pool.pool_index]],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, LiquidityPool>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = FeeAccount::SPACE,
+        seeds = [b"fee_account", pool.key().as_ref(), recipient.as_ref()],
+        bump
+    )]
+    pub fee_account: Account<'info, FeeAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn init_fee_account_handler(ctx: Context<InitFeeAccount>, recipient: Pubkey) -> Result<()> {
+    let fee_account = &mut ctx.accounts.fee_account;
+    fee_account.pool = ctx.accounts.pool.key();
+    fee_account.recipient = recipient;
+    fee_account.bump = ctx.bumps.fee_account;
+    fee_account.total_earned = 0;
+    fee_account.total_claimed = 0;
+
+    Ok(())
+}
@@ -0,0 +1,266 @@
+// programs/liquidity_pool/src/instructions/migrate.rs
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{TokenInterface, TokenAccount, Mint, TransferChecked, MintTo, Burn, mint_to, burn};
+use crate::utils::token::{current_gross_transfer_amount, current_transfer_fee, transfer_tokens_to_vault};
+use crate::state::*;
+use crate::error::*;
+use crate::events::*;
+use crate::utils::curve::Curve;
+use crate::utils::lp::{calculate_initial_lp_amount, calculate_seed_amounts};
+use crate::utils::math::{denormalize_token_amount, normalize_token_amount, safe_sub};
+use crate::utils::vault::{sol_vault_rent_exempt_minimum, transfer_from_sol_vault, QuoteTokenAccounts};
+
+#[derive(Accounts)]
+pub struct Migrate<'info> {
+    #[account(
+        mut,
+        seeds = [b"pool", pool.token_mint.as_ref(), pool.quote_mint.as_ref(), &[pool.pool_index]],
+        bump = pool.bump
+    )]
+    pub pool: Box<Account<'info, LiquidityPool>>,
+
+    #[account(mut, address = pool.token_mint @ LiquidityPoolError::InvalidSwapParams)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [b"pool_token", pool.key().as_ref()],
+        bump
+    )]
+    pub pool_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault", pool.key().as_ref()],
+        bump = pool.sol_vault_bump
+    )]
+    pub sol_vault: SystemAccount<'info>,
+
+    #[account(
+        init,
+        payer = caller,
+        space = AmmPool::SPACE,
+        seeds = [b"amm_pool", token_mint.key().as_ref(), pool.quote_mint.as_ref(), &[pool.pool_index]],
+        bump
+    )]
+    pub amm_pool: Box<Account<'info, AmmPool>>,
+
+    #[account(
+        init,
+        payer = caller,
+        seeds = [b"amm_token", amm_pool.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = amm_pool
+    )]
+    pub amm_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"amm_sol_vault", amm_pool.key().as_ref()],
+        bump
+    )]
+    pub amm_sol_vault: SystemAccount<'info>,
+
+    #[account(
+        init,
+        payer = caller,
+        seeds = [b"amm_lp_mint", amm_pool.key().as_ref()],
+        bump,
+        mint::decimals = LP_MINT_DECIMALS,
+        mint::authority = amm_pool
+    )]
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = caller,
+        seeds = [b"amm_lp_lock", amm_pool.key().as_ref()],
+        bump,
+        token::mint = lp_mint,
+        token::authority = amm_pool
+    )]
+    pub lp_lock_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = pool.quote_mint @ LiquidityPoolError::UnsupportedQuoteMint)]
+    pub quote_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(
+        mut,
+        seeds = [b"quote_vault", pool.key().as_ref()],
+        bump
+    )]
+    pub quote_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        init,
+        payer = caller,
+        seeds = [b"amm_quote", amm_pool.key().as_ref()],
+        bump,
+        token::mint = quote_mint,
+        token::authority = amm_pool,
+        token::token_program = quote_token_program
+    )]
+    pub amm_quote_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn migrate_handler<'info>(ctx: Context<'_, '_, '_, 'info, Migrate<'info>>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    require!(pool.status == PoolStatus::Completed, LiquidityPoolError::InvalidPoolStatus);
+    require!(pool.real_sol > 0 && pool.real_token > 0, LiquidityPoolError::InsufficientLiquidity);
+
+    // The AMM opens at the curve's final spot price. Its token side is sized
+    // on what reaches the vault after any transfer fee; tokens left over are
+    // burned, and SOL left over stays in the launch pool for its LPs.
+    let token_decimals = ctx.accounts.token_mint.decimals;
+    let mint_info = ctx.accounts.token_mint.to_account_info();
+    let spot_price = pool.pricing_curve(token_decimals)?.spot_price()?;
+    let available_tokens = safe_sub(pool.real_token, current_transfer_fee(&mint_info, pool.real_token)?)?;
+    let (sol_amount, seed_tokens) = calculate_seed_amounts(
+        pool.real_sol,
+        normalize_token_amount(available_tokens, token_decimals)?,
+        spot_price,
+    )?;
+    let token_amount = denormalize_token_amount(seed_tokens, token_decimals, false)?;
+    let gross_token_amount = current_gross_transfer_amount(&mint_info, token_amount)?.min(pool.real_token);
+    let burned_token_amount = pool.real_token - gross_token_amount;
+
+    let pool_seeds = pool.signer_seeds();
+
+    let token_amount = transfer_tokens_to_vault(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.pool_token_account.to_account_info(),
+                to: ctx.accounts.amm_token_vault.to_account_info(),
+                mint: mint_info.clone(),
+                authority: pool.to_account_info(),
+            },
+            &[&pool_seeds]
+        )
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
+        &mut ctx.accounts.amm_token_vault,
+        gross_token_amount,
+        token_decimals,
+    )?;
+    require!(token_amount > 0, LiquidityPoolError::InsufficientLiquidity);
+
+    if burned_token_amount > 0 {
+        burn(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: mint_info,
+                    from: ctx.accounts.pool_token_account.to_account_info(),
+                    authority: pool.to_account_info(),
+                },
+                &[&pool_seeds]
+            ),
+            burned_token_amount,
+        )?;
+    }
+
+    let amm_pool = &mut ctx.accounts.amm_pool;
+
+    // Unclaimed fees of pools quoted in an SPL token stay behind in the launch
+    // pool's quote vault, claimable as before.
+    match QuoteTokenAccounts::load(
+        pool.is_native_quote(),
+        ctx.accounts.quote_mint.as_deref(),
+        ctx.accounts.quote_vault.as_deref(),
+        ctx.accounts.amm_quote_vault.as_deref(),
+        ctx.accounts.quote_token_program.as_ref(),
+    )? {
+        Some(quote) => quote.transfer_out(pool.to_account_info(), &pool_seeds, sol_amount)?,
+        None => {
+            // The caller funds the AMM vault's rent-exempt minimum, as the
+            // pool creator did for the launch pool's vault.
+            let amm_sol_vault_rent =
+                sol_vault_rent_exempt_minimum()?.saturating_sub(ctx.accounts.amm_sol_vault.lamports());
+            if amm_sol_vault_rent > 0 {
+                anchor_lang::system_program::transfer(
+                    CpiContext::new(
+                        ctx.accounts.system_program.to_account_info(),
+                        anchor_lang::system_program::Transfer {
+                            from: ctx.accounts.caller.to_account_info(),
+                            to: ctx.accounts.amm_sol_vault.to_account_info(),
+                        },
+                    ),
+                    amm_sol_vault_rent,
+                )?;
+            }
+
+            transfer_from_sol_vault(
+                &ctx.accounts.system_program.to_account_info(),
+                &ctx.accounts.sol_vault.to_account_info(),
+                &ctx.accounts.amm_sol_vault.to_account_info(),
+                &pool.key(),
+                pool.sol_vault_bump,
+                sol_amount,
+            )?
+        },
+    }
+
+    let locked_lp_amount = calculate_initial_lp_amount(sol_amount, token_amount)?;
+
+    amm_pool.token_mint = pool.token_mint;
+    amm_pool.quote_mint = pool.quote_mint;
+    amm_pool.pool_index = pool.pool_index;
+    amm_pool.lp_mint = ctx.accounts.lp_mint.key();
+    amm_pool.launch_pool = pool.key();
+    amm_pool.sol_reserve = sol_amount;
+    amm_pool.token_reserve = token_amount;
+    amm_pool.locked_lp_amount = locked_lp_amount;
+    amm_pool.fee_basis_points = pool.fee_basis_points;
+    amm_pool.bump = ctx.bumps.amm_pool;
+    amm_pool.token_vault_bump = ctx.bumps.amm_token_vault;
+    amm_pool.lp_mint_bump = ctx.bumps.lp_mint;
+    amm_pool.quote_vault_bump = ctx.bumps.amm_quote_vault;
+    amm_pool.sol_vault_bump = ctx.bumps.amm_sol_vault;
+    amm_pool.created_at = current_time;
+
+    let amm_seeds = amm_pool.signer_seeds();
+
+    mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                to: ctx.accounts.lp_lock_account.to_account_info(),
+                authority: amm_pool.to_account_info(),
+            },
+            &[&amm_seeds]
+        ),
+        locked_lp_amount,
+    )?;
+
+    pool.real_sol = safe_sub(pool.real_sol, sol_amount)?;
+    pool.real_token = 0;
+    pool.status = PoolStatus::Migrated;
+    pool.is_active = false;
+
+    emit!(PoolMigrated {
+        pool: pool.key(),
+        token_mint: pool.token_mint,
+        quote_mint: pool.quote_mint,
+        caller: ctx.accounts.caller.key(),
+        amm_pool: amm_pool.key(),
+        sol_amount,
+        token_amount,
+        burned_token_amount,
+        locked_lp_amount,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
@@ -2,15 +2,33 @@
 pub mod create_pool;
 pub mod create_pool_token_account;
 pub mod swap;
+pub mod swap_wsol;
+pub mod swap_via_sol;
 pub mod admin;
 pub mod query;
 pub mod add_liquidity;
 pub mod remove_liquidity;
+pub mod claim_fees;
+pub mod global_config;
+pub mod init_fee_account;
+pub mod migrate;
+pub mod reconcile;
+pub mod amm_liquidity;
+pub mod amm_swap;
 
 pub use create_pool::*;
 pub use create_pool_token_account::*;
 pub use swap::*;
+pub use swap_wsol::*;
+pub use swap_via_sol::*;
 pub use admin::*;
 pub use query::*;
 pub use add_liquidity::*;
-pub use remove_liquidity::*;
\ No newline at end of file
+pub use remove_liquidity::*;
+pub use claim_fees::*;
+pub use global_config::*;
+pub use init_fee_account::*;
+pub use migrate::*;
+pub use reconcile::*;
+pub use amm_liquidity::*;
+pub use amm_swap::*;
\ No newline at end of file
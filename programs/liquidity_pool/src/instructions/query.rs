@@ -1,59 +1,107 @@
 // programs/liquidity_pool/src/instructions/query.rs
 use anchor_lang::prelude::*;
-use crate::state::LiquidityPool;
-use crate::utils::math::{calculate_sol_to_tokens, calculate_tokens_to_sol};
+use anchor_spl::token_interface::Mint;
+use crate::state::{LiquidityPool, Observation, PoolInfo, PoolOracle, SwapDirection, SwapMode, SwapQuote};
+use crate::error::LiquidityPoolError;
+use crate::utils::curve::Curve;
+use crate::utils::math::{normalize_token_amount, u128_to_u64, Price};
+use crate::utils::oracle::calculate_twap;
+use crate::utils::quote::simulate_swap;
+use crate::utils::token::CurrentTransferFee;
 
 #[derive(Accounts)]
 pub struct GetPoolInfo<'info> {
     #[account(
-        seeds = [b"pool", pool.token_mint.as_ref()],
+        seeds = [b"pool", pool.token_mint.as_ref(), pool.quote_mint.as_ref(), &[pool.pool_index]],
         bump = pool.bump
     )]
     pub pool: Account<'info, LiquidityPool>,
+
+    #[account(address = pool.token_mint @ LiquidityPoolError::InvalidSwapParams)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
 }
 
 #[derive(Accounts)]
 pub struct CalculateSwap<'info> {
     #[account(
-        seeds = [b"pool", pool.token_mint.as_ref()],
+        seeds = [b"pool", pool.token_mint.as_ref(), pool.quote_mint.as_ref(), &[pool.pool_index]],
         bump = pool.bump
     )]
     pub pool: Account<'info, LiquidityPool>,
+
+    #[account(address = pool.token_mint @ LiquidityPoolError::InvalidSwapParams)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
 }
 
-pub fn get_pool_info_handler(ctx: Context<GetPoolInfo>) -> Result<()> {
+#[derive(Accounts)]
+pub struct GetTwap<'info> {
+    #[account(
+        seeds = [b"pool", pool.token_mint.as_ref(), pool.quote_mint.as_ref(), &[pool.pool_index]],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, LiquidityPool>,
+
+    #[account(address = pool.token_mint @ LiquidityPoolError::InvalidSwapParams)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"oracle", pool.key().as_ref()],
+        bump = oracle.bump
+    )]
+    pub oracle: Box<Account<'info, PoolOracle>>,
+}
+
+pub fn get_pool_info_handler(ctx: Context<GetPoolInfo>) -> Result<PoolInfo> {
     let pool = &ctx.accounts.pool;
+    let token_mint = &ctx.accounts.token_mint;
 
-    msg!("Pool Info:");
-    msg!("- Address: {}", pool.key());
-    msg!("- Token Mint: {}", pool.token_mint);
-    msg!("- Authority: {}", pool.authority);
-    msg!("- Exchange Rate: {}", pool.exchange_rate);
-    msg!("- SOL Reserve: {}", pool.sol_reserve);
-    msg!("- Token Reserve: {}", pool.token_reserve);
-    msg!("- Active: {}", pool.is_active);
-    msg!("- Created At: {}", pool.created_at);
-
-    Ok(())
+    let curve = pool.pricing_curve(token_mint.decimals)?;
+    let market_cap = curve.market_cap(normalize_token_amount(token_mint.supply, token_mint.decimals)?)?;
+
+    Ok(PoolInfo {
+        pool: pool.key(),
+        authority: pool.authority,
+        token_mint: pool.token_mint,
+        quote_mint: pool.quote_mint,
+        pool_index: pool.pool_index,
+        curve: pool.curve,
+        status: pool.status,
+        is_active: pool.is_active,
+        virtual_sol: pool.virtual_sol,
+        virtual_token: pool.virtual_token,
+        real_sol: pool.real_sol,
+        real_token: pool.real_token,
+        spot_price: Price::new(curve.spot_price()?),
+        fee_basis_points: pool.fee_basis_points,
+        tokens_sold: pool.tokens_sold,
+        completion_threshold: pool.completion_threshold,
+        completion_progress_bps: pool.completion_progress_bps(),
+        market_cap: u128_to_u64(market_cap).unwrap_or(u64::MAX),
+        total_volume_sol: pool.total_volume_sol,
+        total_volume_token: pool.total_volume_token,
+        trade_count: pool.trade_count,
+        created_at: pool.created_at,
+    })
 }
 
 pub fn calculate_swap_handler(
     ctx: Context<CalculateSwap>,
-    input_amount: u64,
-    token_to_sol: bool,
-) -> Result<()> {
+    direction: SwapDirection,
+    mode: SwapMode,
+) -> Result<SwapQuote> {
+    let mint_info = ctx.accounts.token_mint.to_account_info();
+    simulate_swap(&ctx.accounts.pool, ctx.accounts.token_mint.decimals, direction, mode, &CurrentTransferFee(&mint_info))
+}
+
+pub fn get_twap_handler(ctx: Context<GetTwap>, window: u32) -> Result<Price> {
     let pool = &ctx.accounts.pool;
+    let current_time = Clock::get()?.unix_timestamp;
 
-    let output_amount = if token_to_sol {
-        calculate_tokens_to_sol(input_amount, pool.exchange_rate)?
-    } else {
-        calculate_sol_to_tokens(input_amount, pool.exchange_rate)?
+    let spot_price = pool.pricing_curve(ctx.accounts.token_mint.decimals)?.spot_price()?;
+    let current = Observation {
+        timestamp: current_time,
+        price_cumulative: pool.price_cumulative_at(spot_price, current_time),
     };
 
-    msg!("Swap Calculation:");
-    msg!("- Input: {}", input_amount);
-    msg!("- Output: {}", output_amount);
-    msg!("- Direction: {}", if token_to_sol { "Tokens → SOL" } else { "SOL → Tokens" });
-
-    Ok(())
+    Ok(Price::new(calculate_twap(&ctx.accounts.oracle, window, current)?))
 }
\ No newline at end of file
@@ -0,0 +1,253 @@
+// programs/liquidity_pool/src/instructions/reconcile.rs
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{TokenInterface, TokenAccount, Mint, TransferChecked};
+use crate::utils::token::transfer_tokens;
+use crate::state::*;
+use crate::error::*;
+use crate::events::*;
+use crate::utils::auth::check_authority;
+use crate::utils::math::{normalize_token_amount, safe_add, safe_sub};
+use crate::utils::quote::remaining_curve_tokens;
+use crate::utils::vault::{sol_vault_rent_exempt_minimum, transfer_quote_out, QuoteTokenAccounts};
+
+#[derive(Accounts)]
+pub struct SyncPool<'info> {
+    #[account(
+        mut,
+        seeds = [b"pool", pool.token_mint.as_ref(), pool.quote_mint.as_ref(), &[pool.pool_index]],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, LiquidityPool>,
+
+    #[account(
+        seeds = [b"fee_vault", pool.key().as_ref()],
+        bump = fee_vault.bump
+    )]
+    pub fee_vault: Box<Account<'info, FeeVault>>,
+
+    #[account(address = pool.token_mint @ LiquidityPoolError::InvalidSwapParams)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"sol_vault", pool.key().as_ref()],
+        bump = pool.sol_vault_bump
+    )]
+    pub sol_vault: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"pool_token", pool.key().as_ref()],
+        bump
+    )]
+    pub pool_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"quote_vault", pool.key().as_ref()],
+        bump
+    )]
+    pub quote_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SkimPool<'info> {
+    #[account(
+        seeds = [b"pool", pool.token_mint.as_ref(), pool.quote_mint.as_ref(), &[pool.pool_index]],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, LiquidityPool>,
+
+    #[account(
+        seeds = [b"fee_vault", pool.key().as_ref()],
+        bump = fee_vault.bump
+    )]
+    pub fee_vault: Box<Account<'info, FeeVault>>,
+
+    #[account(address = pool.token_mint @ LiquidityPoolError::InvalidSwapParams)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault", pool.key().as_ref()],
+        bump = pool.sol_vault_bump
+    )]
+    pub sol_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"pool_token", pool.key().as_ref()],
+        bump
+    )]
+    pub pool_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub recipient: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = recipient_token_account.mint == pool.token_mint,
+        constraint = recipient_token_account.owner == recipient.key()
+    )]
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = pool.quote_mint @ LiquidityPoolError::UnsupportedQuoteMint)]
+    pub quote_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(
+        mut,
+        seeds = [b"quote_vault", pool.key().as_ref()],
+        bump
+    )]
+    pub quote_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        constraint = recipient_quote_account.mint == pool.quote_mint,
+        constraint = recipient_quote_account.owner == recipient.key()
+    )]
+    pub recipient_quote_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Quote balance backing the real reserve: the sol_vault above its rent-exempt
+/// minimum, or the quote vault less the unclaimed fees it also holds.
+fn available_quote(
+    pool: &LiquidityPool,
+    sol_vault_lamports: u64,
+    quote_vault: Option<&InterfaceAccount<TokenAccount>>,
+    fee_vault: &FeeVault,
+) -> Result<u64> {
+    if pool.is_native_quote() {
+        return Ok(sol_vault_lamports.saturating_sub(sol_vault_rent_exempt_minimum()?));
+    }
+
+    let quote_vault = quote_vault.ok_or(LiquidityPoolError::MissingQuoteAccounts)?;
+    Ok(quote_vault.amount.saturating_sub(fee_vault.unclaimed()))
+}
+
+/// Vault balances above the pool's accounted real reserves, i.e. direct donations.
+fn vault_surplus(pool: &LiquidityPool, available_sol: u64, pool_token_amount: u64) -> Result<(u64, u64)> {
+    Ok((
+        available_sol.saturating_sub(pool.real_sol),
+        pool_token_amount.saturating_sub(pool.real_token),
+    ))
+}
+
+pub fn sync_handler(ctx: Context<SyncPool>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let token_decimals = ctx.accounts.token_mint.decimals;
+
+    let available_sol = available_quote(
+        pool,
+        ctx.accounts.sol_vault.lamports(),
+        ctx.accounts.quote_vault.as_deref(),
+        &ctx.accounts.fee_vault,
+    )?;
+    let (sol_amount, token_amount) = vault_surplus(pool, available_sol, ctx.accounts.pool_token_account.amount)?;
+
+    pool.real_sol = safe_add(pool.real_sol, sol_amount)?;
+    pool.real_token = safe_add(pool.real_token, token_amount)?;
+
+    // Adopted surplus is treated like a deposit: constant-product pools move it
+    // out of the virtual reserves so syncing a donation does not move the price.
+    // A surplus the virtual reserves cannot absorb has to be skimmed instead.
+    if pool.curve == CurveKind::ConstantProduct {
+        let normalized_token_amount = normalize_token_amount(token_amount, token_decimals)?;
+
+        require!(sol_amount <= pool.virtual_sol, LiquidityPoolError::InsufficientLiquidity);
+        require!(normalized_token_amount <= pool.virtual_token, LiquidityPoolError::InsufficientLiquidity);
+        pool.virtual_sol = safe_sub(pool.virtual_sol, sol_amount)?;
+        pool.virtual_token = safe_sub(pool.virtual_token, normalized_token_amount)?;
+    }
+
+    // Adopted SOL counts towards a SolRaised threshold. Once no tokens are left
+    // to sell before it, the pool completes here, as it would on the buy that
+    // reached it.
+    if pool.status == PoolStatus::Trading && remaining_curve_tokens(pool, &pool.pricing_curve(token_decimals)?)? == 0 {
+        pool.status = PoolStatus::Completed;
+
+        emit!(CurveCompleted {
+            pool: pool.key(),
+            token_mint: pool.token_mint,
+            real_sol_reserve: pool.real_sol,
+            tokens_sold: pool.tokens_sold,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+    }
+
+    emit!(ReservesSynced {
+        pool: pool.key(),
+        caller: ctx.accounts.caller.key(),
+        sol_amount,
+        token_amount,
+        real_sol: pool.real_sol,
+        real_token: pool.real_token,
+    });
+
+    Ok(())
+}
+
+pub fn skim_handler<'info>(ctx: Context<'_, '_, '_, 'info, SkimPool<'info>>) -> Result<()> {
+    let pool = &ctx.accounts.pool;
+
+    check_authority(&ctx.accounts.authority.key(), &pool.authority)?;
+
+    let available_sol = available_quote(
+        pool,
+        ctx.accounts.sol_vault.lamports(),
+        ctx.accounts.quote_vault.as_deref(),
+        &ctx.accounts.fee_vault,
+    )?;
+    let (sol_amount, token_amount) = vault_surplus(pool, available_sol, ctx.accounts.pool_token_account.amount)?;
+
+    let quote = QuoteTokenAccounts::load(
+        pool.is_native_quote(),
+        ctx.accounts.quote_mint.as_deref(),
+        ctx.accounts.quote_vault.as_deref(),
+        ctx.accounts.recipient_quote_account.as_deref(),
+        ctx.accounts.quote_token_program.as_ref(),
+    )?;
+    transfer_quote_out(
+        quote.as_ref(),
+        pool,
+        &ctx.accounts.sol_vault.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.recipient.to_account_info(),
+        sol_amount,
+    )?;
+
+    if token_amount > 0 {
+        let seeds = pool.signer_seeds();
+
+        transfer_tokens(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.pool_token_account.to_account_info(),
+                    to: ctx.accounts.recipient_token_account.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    authority: pool.to_account_info(),
+                },
+                &[&seeds]
+            )
+            .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
+            token_amount,
+            ctx.accounts.token_mint.decimals,
+        )?;
+    }
+
+    emit!(ReservesSkimmed {
+        pool: pool.key(),
+        authority: ctx.accounts.authority.key(),
+        recipient: ctx.accounts.recipient.key(),
+        sol_amount,
+        token_amount,
+    });
+
+    Ok(())
+}
@@ -1,71 +1,155 @@
 // programs/liquidity_pool/src/instructions/remove_liquidity.rs
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount, Mint, TransferChecked, transfer_checked};
+use anchor_spl::token_interface::{TokenInterface, TokenAccount, Mint, TransferChecked, Burn, burn};
+use crate::utils::token::transfer_tokens;
 use crate::state::*;
 use crate::error::*;
 use crate::events::*;
+use crate::utils::lp::calculate_withdrawal;
+use crate::utils::math::{normalize_token_amount, safe_add, safe_sub};
+use crate::utils::validation::validate_token_decimals;
+use crate::utils::vault::{transfer_quote_out, QuoteTokenAccounts};
 
 #[derive(Accounts)]
 pub struct RemoveLiquidity<'info> {
     #[account(
         mut,
-        seeds = [b"pool", pool.token_mint.as_ref()],
-        bump = pool.bump,
-        constraint = pool.authority == user.key() @ LiquidityPoolError::UnauthorizedAccess
+        seeds = [b"pool", pool.token_mint.as_ref(), pool.quote_mint.as_ref(), &[pool.pool_index]],
+        bump = pool.bump
     )]
     pub pool: Account<'info, LiquidityPool>,
 
     #[account(mut)]
     pub user: Signer<'info>,
 
-    pub token_mint: Account<'info, Mint>,
+    #[account(address = pool.token_mint @ LiquidityPoolError::InvalidSwapParams)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
         constraint = user_token_account.mint == pool.token_mint,
         constraint = user_token_account.owner == user.key()
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
-        seeds = [b"pool_token", pool.token_mint.as_ref()],
+        seeds = [b"pool_token", pool.key().as_ref()],
         bump
     )]
-    pub pool_token_account: Account<'info, TokenAccount>,
+    pub pool_token_account: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
-}
+    #[account(
+        mut,
+        seeds = [b"sol_vault", pool.key().as_ref()],
+        bump = pool.sol_vault_bump
+    )]
+    pub sol_vault: SystemAccount<'info>,
 
-pub fn remove_liquidity_handler(
-    ctx: Context<RemoveLiquidity>,
-    token_decimals: u8,
-) -> Result<()> {
-    let pool = &mut ctx.accounts.pool;
-    let user = &ctx.accounts.user;
+    #[account(address = pool.quote_mint @ LiquidityPoolError::UnsupportedQuoteMint)]
+    pub quote_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
 
-    require!(token_decimals <= 18, LiquidityPoolError::InvalidSwapParams);
+    #[account(
+        mut,
+        seeds = [b"quote_vault", pool.key().as_ref()],
+        bump
+    )]
+    pub quote_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
-    let sol_to_withdraw = pool.sol_reserve;
-    let pool_token_balance = ctx.accounts.pool_token_account.amount;
+    #[account(
+        mut,
+        constraint = user_quote_account.mint == pool.quote_mint,
+        constraint = user_quote_account.owner == user.key()
+    )]
+    pub user_quote_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
-    require!(sol_to_withdraw > 0 || pool_token_balance > 0, LiquidityPoolError::InsufficientLiquidity);
+    #[account(
+        mut,
+        seeds = [b"lp_mint", pool.key().as_ref()],
+        bump
+    )]
+    pub lp_mint: InterfaceAccount<'info, Mint>,
 
-    if sol_to_withdraw > 0 {
-        require!(
-            pool.to_account_info().lamports() >= sol_to_withdraw,
-            LiquidityPoolError::InsufficientLiquidity
-        );
+    #[account(
+        mut,
+        constraint = user_lp_account.mint == lp_mint.key(),
+        constraint = user_lp_account.owner == user.key()
+    )]
+    pub user_lp_account: InterfaceAccount<'info, TokenAccount>,
 
-        **pool.to_account_info().try_borrow_mut_lamports()? -= sol_to_withdraw;
-        **user.to_account_info().try_borrow_mut_lamports()? += sol_to_withdraw;
-    }
+    pub token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
 
-    if pool_token_balance > 0 {
-        let seeds = &[b"pool", pool.token_mint.as_ref(), &[pool.bump]];
+pub fn remove_liquidity_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, RemoveLiquidity<'info>>,
+    lp_amount: u64,
+    min_sol_amount: u64,
+    min_token_amount: u64,
+    token_decimals: u8,
+) -> Result<()> {
+    require!(token_decimals == ctx.accounts.token_mint.decimals, LiquidityPoolError::InvalidTokenDecimals);
+    remove_liquidity_v2_handler(ctx, lp_amount, min_sol_amount, min_token_amount)
+}
 
-        transfer_checked(
+pub fn remove_liquidity_v2_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, RemoveLiquidity<'info>>,
+    lp_amount: u64,
+    min_sol_amount: u64,
+    min_token_amount: u64,
+) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let user = &ctx.accounts.user;
+    let token_decimals = ctx.accounts.token_mint.decimals;
+    let lp_supply = ctx.accounts.lp_mint.supply;
+
+    validate_token_decimals(token_decimals)?;
+    require!(pool.status != PoolStatus::Completed, LiquidityPoolError::InvalidPoolStatus);
+
+    let (sol_to_withdraw, tokens_to_withdraw) = calculate_withdrawal(
+        lp_amount,
+        lp_supply,
+        pool.real_sol,
+        pool.real_token,
+    )?;
+
+    require!(sol_to_withdraw > 0 || tokens_to_withdraw > 0, LiquidityPoolError::InsufficientLiquidity);
+    require!(sol_to_withdraw >= min_sol_amount, LiquidityPoolError::SlippageExceeded);
+    require!(tokens_to_withdraw >= min_token_amount, LiquidityPoolError::SlippageExceeded);
+
+    burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                from: ctx.accounts.user_lp_account.to_account_info(),
+                authority: user.to_account_info(),
+            },
+        ),
+        lp_amount,
+    )?;
+
+    let quote = QuoteTokenAccounts::load(
+        pool.is_native_quote(),
+        ctx.accounts.quote_mint.as_deref(),
+        ctx.accounts.quote_vault.as_deref(),
+        ctx.accounts.user_quote_account.as_deref(),
+        ctx.accounts.quote_token_program.as_ref(),
+    )?;
+    transfer_quote_out(
+        quote.as_ref(),
+        pool,
+        &ctx.accounts.sol_vault.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &user.to_account_info(),
+        sol_to_withdraw,
+    )?;
+
+    if tokens_to_withdraw > 0 {
+        let seeds = pool.signer_seeds();
+
+        transfer_tokens(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
                 TransferChecked {
@@ -74,25 +158,32 @@ pub fn remove_liquidity_handler(
                     mint: ctx.accounts.token_mint.to_account_info(),
                     authority: pool.to_account_info(),
                 },
-                &[seeds]
-            ),
-            pool_token_balance,
+                &[&seeds]
+            )
+            .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
+            tokens_to_withdraw,
             token_decimals,
         )?;
     }
 
+    pool.real_sol = safe_sub(pool.real_sol, sol_to_withdraw)?;
+    pool.real_token = safe_sub(pool.real_token, tokens_to_withdraw)?;
+
+    if pool.curve == CurveKind::ConstantProduct {
+        pool.virtual_sol = safe_add(pool.virtual_sol, sol_to_withdraw)?;
+        pool.virtual_token = safe_add(pool.virtual_token, normalize_token_amount(tokens_to_withdraw, token_decimals)?)?;
+    }
+
     emit!(LiquidityRemoved {
         pool: pool.key(),
         liquidity_provider: user.key(),
+        quote_mint: pool.quote_mint,
         sol_amount: sol_to_withdraw,
-        token_amount: pool_token_balance,
+        token_amount: tokens_to_withdraw,
+        lp_amount,
         dex_name: DEX_NAME.to_string(),
         transaction_type: "remove_liquidity".to_string(),
     });
 
-    pool.sol_reserve = 0;
-    pool.token_reserve = 0;
-    pool.is_active = false;
-
     Ok(())
-}
\ No newline at end of file
+}
@@ -1,91 +1,113 @@
 // programs/liquidity_pool/src/instructions/swap.rs
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount, Mint, TransferChecked, transfer_checked};
+use anchor_spl::token_interface::{TokenInterface, TokenAccount, Mint, TransferChecked};
+use crate::utils::token::{transfer_tokens, transfer_tokens_to_vault, CurrentTransferFee};
 use crate::state::*;
 use crate::error::*;
 use crate::events::*;
 use crate::utils::math::*;
+use crate::utils::quote::{apply_swap, quote_swap, CurveSwap};
+use crate::utils::validation::validate_token_decimals;
+use crate::utils::vault::{transfer_from_sol_vault, transfer_quote_in, transfer_quote_out, QuoteTokenAccounts};
 
 #[derive(Accounts)]
-pub struct SwapTokensForSol<'info> {
+pub struct Swap<'info> {
     #[account(
         mut,
-        seeds = [b"pool", pool.token_mint.as_ref()],
+        seeds = [b"pool", pool.token_mint.as_ref(), pool.quote_mint.as_ref(), &[pool.pool_index]],
         bump = pool.bump
     )]
     pub pool: Account<'info, LiquidityPool>,
 
-    #[account(mut)]
-    pub user: Signer<'info>,
-
     #[account(
-        constraint = program_authority.key() == crate::ID @ LiquidityPoolError::UnauthorizedAccess
+        mut,
+        seeds = [b"fee_vault", pool.key().as_ref()],
+        bump = fee_vault.bump
     )]
-    pub program_authority: Signer<'info>,
-
-    pub token_mint: Account<'info, Mint>,
+    pub fee_vault: Box<Account<'info, FeeVault>>,
 
     #[account(
         mut,
-        constraint = user_token_account.mint == pool.token_mint,
-        constraint = user_token_account.owner == user.key()
+        seeds = [b"sol_vault", pool.key().as_ref()],
+        bump = pool.sol_vault_bump
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub sol_vault: SystemAccount<'info>,
 
     #[account(
         mut,
-        seeds = [b"pool_token", pool.token_mint.as_ref()],
-        bump
+        seeds = [b"oracle", pool.key().as_ref()],
+        bump = oracle.bump
     )]
-    pub pool_token_account: Account<'info, TokenAccount>,
+    pub oracle: Box<Account<'info, PoolOracle>>,
 
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump
+    )]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
 
-    /// CHECK: This is a PDA used for CPI event authority
     #[account(
-        seeds = [b"__event_authority"],
-        bump
+        mut,
+        seeds = [b"fee_account", pool.key().as_ref(), b"protocol"],
+        bump = protocol_fee_account.bump
     )]
-    pub event_authority: UncheckedAccount<'info>,
+    pub protocol_fee_account: Box<Account<'info, FeeAccount>>,
 
-    pub program: Program<'info, crate::program::LiquidityPool>,
-}
+    #[account(
+        mut,
+        seeds = [b"fee_account", pool.key().as_ref(), pool.authority.as_ref()],
+        bump = creator_fee_account.bump
+    )]
+    pub creator_fee_account: Box<Account<'info, FeeAccount>>,
 
-#[derive(Accounts)]
-pub struct SwapSolForTokens<'info> {
     #[account(
         mut,
-        seeds = [b"pool", pool.token_mint.as_ref()],
-        bump = pool.bump
+        seeds = [b"fee_account", pool.key().as_ref(), referrer_fee_account.recipient.as_ref()],
+        bump = referrer_fee_account.bump,
+        constraint = referrer_fee_account.key() != protocol_fee_account.key()
+            && referrer_fee_account.key() != creator_fee_account.key() @ LiquidityPoolError::InvalidSwapParams
     )]
-    pub pool: Account<'info, LiquidityPool>,
+    pub referrer_fee_account: Option<Box<Account<'info, FeeAccount>>>,
 
     #[account(mut)]
     pub user: Signer<'info>,
 
-    #[account(
-        constraint = program_authority.key() == crate::ID @ LiquidityPoolError::UnauthorizedAccess
-    )]
-    pub program_authority: Signer<'info>,
-
-    pub token_mint: Account<'info, Mint>,
+    #[account(address = pool.token_mint @ LiquidityPoolError::InvalidSwapParams)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
         constraint = user_token_account.mint == pool.token_mint,
         constraint = user_token_account.owner == user.key()
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
-        seeds = [b"pool_token", pool.token_mint.as_ref()],
+        seeds = [b"pool_token", pool.key().as_ref()],
         bump
     )]
-    pub pool_token_account: Account<'info, TokenAccount>,
+    pub pool_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = pool.quote_mint @ LiquidityPoolError::UnsupportedQuoteMint)]
+    pub quote_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
 
-    pub token_program: Program<'info, Token>,
+    #[account(
+        mut,
+        seeds = [b"quote_vault", pool.key().as_ref()],
+        bump
+    )]
+    pub quote_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        constraint = user_quote_account.mint == pool.quote_mint,
+        constraint = user_quote_account.owner == user.key()
+    )]
+    pub user_quote_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Option<Interface<'info, TokenInterface>>,
     pub system_program: Program<'info, System>,
 
     /// CHECK: This is a PDA used for CPI event authority
@@ -98,230 +120,267 @@ pub struct SwapSolForTokens<'info> {
     pub program: Program<'info, crate::program::LiquidityPool>,
 }
 
-pub fn swap_tokens_for_sol_handler(
-    ctx: Context<SwapTokensForSol>,
-    token_amount: u64,
-    sol_output: u64,
-    token_decimals: u8,
-) -> Result<()> {
-    let pool = &mut ctx.accounts.pool;
-    let current_time = Clock::get()?.unix_timestamp;
-
-    require!(pool.is_active, LiquidityPoolError::PoolInactive);
-    require!(token_decimals <= 18, LiquidityPoolError::InvalidSwapParams);
-
-    const AUTO_VALUE: u64 = u64::MAX;
-
-    let (final_token_amount, final_sol_output) = match (token_amount == AUTO_VALUE, sol_output == AUTO_VALUE) {
-        (false, true) => {
-            require!(token_amount > 0, LiquidityPoolError::InvalidSwapAmount);
-
-            let normalized_token_input = normalize_token_amount(token_amount, token_decimals)?;
+/// Prices the swap against the pool's curve. Sold tokens are pulled into the
+/// vault before the swap settles, and must arrive as quoted after the mint's
+/// transfer fee.
+fn price_swap<'info>(
+    accounts: &mut Swap<'info>,
+    remaining_accounts: &[AccountInfo<'info>],
+    direction: SwapDirection,
+    mode: SwapMode,
+) -> Result<CurveSwap> {
+    let mint_info = accounts.token_mint.to_account_info();
+    let swap = quote_swap(
+        &accounts.pool,
+        accounts.token_mint.decimals,
+        direction,
+        mode,
+        &CurrentTransferFee(&mint_info),
+    )?;
 
-            let rent = Rent::get()?;
-            let rent_exempt_minimum = rent.minimum_balance(std::mem::size_of::<LiquidityPool>());
+    if direction == SwapDirection::Sell {
+        let received = transfer_sold_tokens(accounts, remaining_accounts, swap.user_token_amount)?;
+        require!(received >= swap.token_amount, LiquidityPoolError::VaultTransferShortfall);
+    }
 
-            let (real_sol, real_tokens) = get_real_reserves_from_accounts(
-                pool.to_account_info().lamports(),
-                rent_exempt_minimum,
-                ctx.accounts.pool_token_account.amount,
-                token_decimals,
-            )?;
-
-            let (effective_sol_reserve, effective_token_reserve) = get_effective_pool_reserves(
-                pool.sol_reserve,
-                pool.token_reserve,
-                real_sol,
-                real_tokens,
-            )?;
+    Ok(swap)
+}
 
-            require!(effective_sol_reserve > 0 && effective_token_reserve > 0, LiquidityPoolError::InsufficientLiquidity);
+fn transfer_sold_tokens<'info>(
+    accounts: &mut Swap<'info>,
+    remaining_accounts: &[AccountInfo<'info>],
+    amount: u64,
+) -> Result<u64> {
+    transfer_tokens_to_vault(
+        CpiContext::new(
+            accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: accounts.user_token_account.to_account_info(),
+                to: accounts.pool_token_account.to_account_info(),
+                mint: accounts.token_mint.to_account_info(),
+                authority: accounts.user.to_account_info(),
+            },
+        )
+        .with_remaining_accounts(remaining_accounts.to_vec()),
+        &mut accounts.pool_token_account,
+        amount,
+        accounts.token_mint.decimals,
+    )
+}
 
-            let calculated_sol_output = calculate_swap_output_amount(
-                normalized_token_input,
-                effective_token_reserve,
-                effective_sol_reserve,
-            )?;
+pub fn swap_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, Swap<'info>>,
+    direction: SwapDirection,
+    mode: SwapMode,
+    token_decimals: u8,
+) -> Result<()> {
+    require!(token_decimals == ctx.accounts.token_mint.decimals, LiquidityPoolError::InvalidTokenDecimals);
+    swap_v2_handler(ctx, direction, mode)
+}
 
-            (token_amount, calculated_sol_output)
-        },
-        _ => {
-            return Err(LiquidityPoolError::InvalidSwapParams.into());
-        }
-    };
+pub fn swap_v2_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, Swap<'info>>,
+    direction: SwapDirection,
+    mode: SwapMode,
+) -> Result<()> {
+    execute_swap(ctx.accounts, ctx.remaining_accounts, direction, mode, SolSettlement::User)?;
+    Ok(())
+}
 
-    require!(final_sol_output > 0, LiquidityPoolError::InvalidSwapAmount);
+/// Where the SOL leg of a swap on a SOL pool settles. Pools quoted in an SPL
+/// token always settle in the user's quote token account.
+#[derive(Clone, Copy)]
+pub enum SolSettlement<'a, 'info> {
+    /// Paid from and into the user signer's lamports.
+    User,
+    /// The caller has already moved a buy's SOL into the SOL vault, and a
+    /// sell's SOL is paid into this account.
+    Account(&'a AccountInfo<'info>),
+}
 
-    let current_account_balance = pool.to_account_info().lamports();
-    let rent = Rent::get()?;
-    let rent_exempt_minimum = rent.minimum_balance(std::mem::size_of::<LiquidityPool>());
+/// Runs a swap settled against the user signer, in native SOL or the user's
+/// quote token account, and returns what was executed.
+pub fn execute_swap<'info>(
+    accounts: &mut Swap<'info>,
+    remaining_accounts: &[AccountInfo<'info>],
+    direction: SwapDirection,
+    mode: SwapMode,
+    settlement: SolSettlement<'_, 'info>,
+) -> Result<CurveSwap> {
+    let current_time = Clock::get()?.unix_timestamp;
+    let token_decimals = accounts.token_mint.decimals;
+
+    require!(!accounts.global_config.is_paused, LiquidityPoolError::ProtocolPaused);
+    require!(accounts.pool.is_active, LiquidityPoolError::PoolInactive);
+    require!(accounts.pool.status == PoolStatus::Trading, LiquidityPoolError::CurveCompleted);
+    validate_token_decimals(token_decimals)?;
+
+    let swap = price_swap(accounts, remaining_accounts, direction, mode)?;
+    let quote = QuoteTokenAccounts::load(
+        accounts.pool.is_native_quote(),
+        accounts.quote_mint.as_deref(),
+        accounts.quote_vault.as_deref(),
+        accounts.user_quote_account.as_deref(),
+        accounts.quote_token_program.as_ref(),
+    )?;
+    if let SolSettlement::Account(_) = settlement {
+        require!(quote.is_none(), LiquidityPoolError::UnsupportedQuoteMint);
+    }
+    let pool = &mut accounts.pool;
+    let CurveSwap { token_amount, normalized_token_amount, sol_amount, curve_sol_amount, fee_amount, completes_curve, .. } = swap;
+    let execution_price = Price::from_amounts(sol_amount, normalized_token_amount)?;
 
-    require!(
-        current_account_balance >= rent_exempt_minimum.checked_add(final_sol_output)
-            .ok_or(LiquidityPoolError::MathOverflow)?,
-        LiquidityPoolError::InsufficientLiquidity
-    );
+    pool.accumulate_price(swap.spot_price, current_time);
+    accounts.oracle.record(current_time, pool.price_cumulative);
 
     emit!(SwapExecuted {
         pool: pool.key(),
-        trader: ctx.accounts.user.key(),
+        trader: accounts.user.key(),
         token_mint: pool.token_mint,
+        quote_mint: pool.quote_mint,
         dex_name: DEX_NAME.to_string(),
-        trade_direction: "sell".to_string(),
-        token_amount: final_token_amount,
-        sol_amount: final_sol_output,
-        exchange_rate: if final_token_amount > 0 { (final_sol_output as f64) / (final_token_amount as f64) } else { 0.0 },
+        trade_direction: match direction {
+            SwapDirection::Buy => "buy".to_string(),
+            SwapDirection::Sell => "sell".to_string(),
+        },
+        token_amount,
+        sol_amount,
+        exchange_rate: execution_price,
         timestamp: current_time,
         transaction_type: TRANSACTION_TYPE.to_string(),
         pool_type: POOL_TYPE.to_string(),
-        slippage: 0.0,
-        fee_amount: 0,
+        slippage_bps: swap.price_impact_bps,
+        fee_amount,
         program_version: PROGRAM_VERSION.to_string(),
         network: get_network_name(),
     });
 
-    transfer_checked(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            TransferChecked {
-                from: ctx.accounts.user_token_account.to_account_info(),
-                to: ctx.accounts.pool_token_account.to_account_info(),
-                mint: ctx.accounts.token_mint.to_account_info(),
-                authority: ctx.accounts.user.to_account_info(),
-            },
-        ),
-        final_token_amount,
-        token_decimals,
-    )?;
-
-    **pool.to_account_info().try_borrow_mut_lamports()? -= final_sol_output;
-    **ctx.accounts.user.to_account_info().try_borrow_mut_lamports()? += final_sol_output;
-
-    let normalized_final_token_amount = normalize_token_amount(final_token_amount, token_decimals)?;
-    pool.token_reserve = pool.token_reserve.checked_add(normalized_final_token_amount)
-        .ok_or(LiquidityPoolError::MathOverflow)?;
-
-    pool.update_trade_stats(final_sol_output, final_token_amount, current_time);
-
-    Ok(())
-}
-
-pub fn swap_sol_for_tokens_handler(
-    ctx: Context<SwapSolForTokens>,
-    sol_amount: u64,
-    token_output: u64,
-    token_decimals: u8,
-) -> Result<()> {
-    let pool = &mut ctx.accounts.pool;
-    let current_time = Clock::get()?.unix_timestamp;
-
-    require!(pool.is_active, LiquidityPoolError::PoolInactive);
-    require!(token_decimals <= 18, LiquidityPoolError::InvalidSwapParams);
-
-    const AUTO_VALUE: u64 = u64::MAX;
-
-    let (final_sol_amount, final_token_output) = match (sol_amount == AUTO_VALUE, token_output == AUTO_VALUE) {
-        (true, false) => {
-            require!(token_output > 0, LiquidityPoolError::InvalidSwapAmount);
-
-            let normalized_token_output = normalize_token_amount(token_output, token_decimals)?;
-
-            let rent = Rent::get()?;
-            let rent_exempt_minimum = rent.minimum_balance(std::mem::size_of::<LiquidityPool>());
-
-            let (real_sol, real_tokens) = get_real_reserves_from_accounts(
-                pool.to_account_info().lamports(),
-                rent_exempt_minimum,
-                ctx.accounts.pool_token_account.amount,
+    match direction {
+        SwapDirection::Buy => {
+            let user = accounts.user.to_account_info();
+            let system_program = accounts.system_program.to_account_info();
+
+            // SOL pools hold fees as lamports on the fee vault. Other pools
+            // leave them in the quote vault, tracked by the fee vault totals.
+            match (quote.as_ref(), settlement) {
+                (Some(_), _) => transfer_quote_in(
+                    quote.as_ref(),
+                    &user,
+                    &accounts.sol_vault.to_account_info(),
+                    &system_program,
+                    safe_add(curve_sol_amount, fee_amount)?,
+                )?,
+                (None, SolSettlement::User) => {
+                    transfer_quote_in(None, &user, &accounts.sol_vault.to_account_info(), &system_program, curve_sol_amount)?;
+                    transfer_quote_in(None, &user, &accounts.fee_vault.to_account_info(), &system_program, fee_amount)?;
+                },
+                (None, SolSettlement::Account(_)) => transfer_from_sol_vault(
+                    &system_program,
+                    &accounts.sol_vault.to_account_info(),
+                    &accounts.fee_vault.to_account_info(),
+                    &pool.key(),
+                    pool.sol_vault_bump,
+                    fee_amount,
+                )?,
+            }
+
+            let seeds = pool.signer_seeds();
+
+            transfer_tokens(
+                CpiContext::new_with_signer(
+                    accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: accounts.pool_token_account.to_account_info(),
+                        to: accounts.user_token_account.to_account_info(),
+                        mint: accounts.token_mint.to_account_info(),
+                        authority: pool.to_account_info(),
+                    },
+                    &[&seeds]
+                )
+                .with_remaining_accounts(remaining_accounts.to_vec()),
+                token_amount,
                 token_decimals,
             )?;
 
-            let (effective_sol_reserve, effective_token_reserve) = get_effective_pool_reserves(
-                pool.sol_reserve,
-                pool.token_reserve,
-                real_sol,
-                real_tokens,
-            )?;
-
-            require!(effective_sol_reserve > 0 && effective_token_reserve > 0, LiquidityPoolError::InsufficientLiquidity);
-
-            let calculated_sol_amount = calculate_swap_input_amount(
-                normalized_token_output,
-                effective_sol_reserve,
-                effective_token_reserve,
+        },
+        SwapDirection::Sell => {
+            let recipient = match settlement {
+                SolSettlement::User => accounts.user.to_account_info(),
+                SolSettlement::Account(account) => account.clone(),
+            };
+            transfer_quote_out(
+                quote.as_ref(),
+                pool,
+                &accounts.sol_vault.to_account_info(),
+                &accounts.system_program.to_account_info(),
+                &recipient,
+                sol_amount,
             )?;
+            if quote.is_none() {
+                transfer_from_sol_vault(
+                    &accounts.system_program.to_account_info(),
+                    &accounts.sol_vault.to_account_info(),
+                    &accounts.fee_vault.to_account_info(),
+                    &pool.key(),
+                    pool.sol_vault_bump,
+                    fee_amount,
+                )?;
+            }
 
-            (calculated_sol_amount, token_output)
         },
-        _ => {
-            return Err(LiquidityPoolError::InvalidSwapParams.into());
-        }
-    };
-
-    require!(final_token_output > 0, LiquidityPoolError::InvalidSwapAmount);
-    require!(final_token_output <= ctx.accounts.pool_token_account.amount, LiquidityPoolError::InsufficientLiquidity);
-    require!(final_sol_amount > 0, LiquidityPoolError::InvalidSwapAmount);
-
-    let rent = Rent::get()?;
-    let rent_exempt_minimum = rent.minimum_balance(std::mem::size_of::<LiquidityPool>());
-    let current_account_balance = pool.to_account_info().lamports();
-    require!(
-        current_account_balance >= rent_exempt_minimum.checked_add(final_sol_amount)
-            .ok_or(LiquidityPoolError::MathOverflow)?,
-        LiquidityPoolError::InsufficientLiquidity
-    );
+    }
 
-    emit!(SwapExecuted {
-        pool: pool.key(),
-        trader: ctx.accounts.user.key(),
-        token_mint: pool.token_mint,
-        dex_name: DEX_NAME.to_string(),
-        trade_direction: "buy".to_string(),
-        token_amount: final_token_output,
-        sol_amount: final_sol_amount,
-        exchange_rate: if final_token_output > 0 { (final_sol_amount as f64) / (final_token_output as f64) } else { 0.0 },
-        timestamp: current_time,
-        transaction_type: TRANSACTION_TYPE.to_string(),
-        pool_type: POOL_TYPE.to_string(),
-        slippage: 0.0,
-        fee_amount: 0,
-        program_version: PROGRAM_VERSION.to_string(),
-        network: get_network_name(),
-    });
+    apply_swap(pool, direction, &swap)?;
 
-    anchor_lang::system_program::transfer(
-        CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            anchor_lang::system_program::Transfer {
-                from: ctx.accounts.user.to_account_info(),
-                to: pool.to_account_info(),
-            },
-        ),
-        final_sol_amount,
-    )?;
+    let fee_vault = &mut accounts.fee_vault;
+    fee_vault.total_collected = safe_add(fee_vault.total_collected, fee_amount)?;
 
-    let seeds = &[b"pool", pool.token_mint.as_ref(), &[pool.bump]];
+    if fee_amount > 0 {
+        let global_config = &accounts.global_config;
+        let (protocol_fee, creator_fee, referrer_fee) = split_fee(
+            fee_amount,
+            global_config.creator_share_bps,
+            global_config.referrer_share_bps,
+            accounts.referrer_fee_account.is_some(),
+        )?;
 
-    transfer_checked(
-        CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            TransferChecked {
-                from: ctx.accounts.pool_token_account.to_account_info(),
-                to: ctx.accounts.user_token_account.to_account_info(),
-                mint: ctx.accounts.token_mint.to_account_info(),
-                authority: pool.to_account_info(),
-            },
-            &[seeds]
-        ),
-        final_token_output,
-        token_decimals,
-    )?;
-
-    let normalized_final_token_output = normalize_token_amount(final_token_output, token_decimals)?;
-    pool.token_reserve = pool.token_reserve.checked_sub(normalized_final_token_output)
-        .ok_or(LiquidityPoolError::MathOverflow)?;
+        let protocol_fee_account = &mut accounts.protocol_fee_account;
+        protocol_fee_account.total_earned = safe_add(protocol_fee_account.total_earned, protocol_fee)?;
 
-    pool.update_trade_stats(final_sol_amount, final_token_output, current_time);
+        let creator_fee_account = &mut accounts.creator_fee_account;
+        creator_fee_account.total_earned = safe_add(creator_fee_account.total_earned, creator_fee)?;
 
-    Ok(())
-}
\ No newline at end of file
+        let referrer = match accounts.referrer_fee_account.as_mut() {
+            Some(referrer_fee_account) => {
+                referrer_fee_account.total_earned = safe_add(referrer_fee_account.total_earned, referrer_fee)?;
+                Some(referrer_fee_account.recipient)
+            },
+            None => None,
+        };
+
+        emit!(FeesDistributed {
+            pool: pool.key(),
+            protocol_fee,
+            creator: pool.authority,
+            creator_fee,
+            referrer,
+            referrer_fee,
+        });
+    }
+
+    pool.update_trade_stats(sol_amount, token_amount, execution_price, current_time);
+
+    if completes_curve {
+        pool.status = PoolStatus::Completed;
+
+        emit!(CurveCompleted {
+            pool: pool.key(),
+            token_mint: pool.token_mint,
+            real_sol_reserve: pool.real_sol,
+            tokens_sold: pool.tokens_sold,
+            timestamp: current_time,
+        });
+    }
+
+    Ok(swap)
+}
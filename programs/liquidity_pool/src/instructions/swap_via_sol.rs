@@ -0,0 +1,75 @@
+// programs/liquidity_pool/src/instructions/swap_via_sol.rs
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::*;
+use crate::events::*;
+use crate::instructions::swap::*;
+use crate::utils::math::safe_sub;
+
+/// Token-to-token route through SOL, or any quote mint the two pools share:
+/// `sell` is the pool of the input token and `buy` the pool of the output
+/// token. Both legs are signed by the same user.
+#[derive(Accounts)]
+pub struct SwapViaSol<'info> {
+    pub sell: Swap<'info>,
+    pub buy: Swap<'info>,
+}
+
+pub fn swap_via_sol_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, SwapViaSol<'info>>,
+    amount_in: u64,
+    min_amount_out: u64,
+) -> Result<()> {
+    let accounts = ctx.accounts;
+
+    require!(accounts.sell.pool.key() != accounts.buy.pool.key(), LiquidityPoolError::InvalidSwapParams);
+    require!(accounts.sell.user.key() == accounts.buy.user.key(), LiquidityPoolError::InvalidSwapParams);
+    require!(
+        accounts.sell.pool.quote_mint == accounts.buy.pool.quote_mint,
+        LiquidityPoolError::UnsupportedQuoteMint
+    );
+
+    let sold = execute_swap(
+        &mut accounts.sell,
+        ctx.remaining_accounts,
+        SwapDirection::Sell,
+        SwapMode::ExactIn { amount_in, min_amount_out: 0 },
+        SolSettlement::User,
+    )?;
+    let sol_amount = sold.amount_out(SwapDirection::Sell);
+
+    // Read after the sell leg: both pools may trade the same mint, in which
+    // case the sell has just drawn on the account the buy pays into.
+    accounts.buy.user_token_account.reload()?;
+    let balance_before = accounts.buy.user_token_account.amount;
+
+    execute_swap(
+        &mut accounts.buy,
+        ctx.remaining_accounts,
+        SwapDirection::Buy,
+        SwapMode::ExactIn { amount_in: sol_amount, min_amount_out: 0 },
+        SolSettlement::User,
+    )?;
+
+    // Slippage is checked once, on what actually reached the user after any
+    // transfer fee of the output mint.
+    accounts.buy.user_token_account.reload()?;
+    let amount_out = safe_sub(accounts.buy.user_token_account.amount, balance_before)?;
+    require!(amount_out >= min_amount_out, LiquidityPoolError::SlippageExceeded);
+
+    emit!(RoutedSwapExecuted {
+        trader: accounts.sell.user.key(),
+        input_pool: accounts.sell.pool.key(),
+        input_mint: accounts.sell.pool.token_mint,
+        output_pool: accounts.buy.pool.key(),
+        output_mint: accounts.buy.pool.token_mint,
+        quote_mint: accounts.buy.pool.quote_mint,
+        amount_in,
+        sol_amount,
+        amount_out,
+        dex_name: DEX_NAME.to_string(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
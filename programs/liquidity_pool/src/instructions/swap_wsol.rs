@@ -0,0 +1,157 @@
+// programs/liquidity_pool/src/instructions/swap_wsol.rs
+use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token::native_mint;
+use anchor_spl::token::{self, CloseAccount, Mint, SyncNative, Token, TokenAccount, TransferChecked};
+use crate::state::*;
+use crate::error::LiquidityPoolError;
+use crate::instructions::swap::*;
+use crate::utils::quote::quote_swap;
+use crate::utils::token::CurrentTransferFee;
+use crate::utils::vault::transfer_from_sol_vault;
+
+/// `swap_v2` settled in wrapped SOL, for SOL pools only. The pool still trades
+/// native lamports. The SOL leg moves between `user_wsol_account` and the
+/// SOL vault, and the user only fronts the rent of a buy's ephemeral WSOL
+/// account.
+#[derive(Accounts)]
+pub struct SwapWsol<'info> {
+    pub swap: Swap<'info>,
+
+    #[account(address = native_mint::ID)]
+    pub wsol_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = wsol_mint,
+        token::authority = swap.user
+    )]
+    pub user_wsol_account: Box<Account<'info, TokenAccount>>,
+
+    /// Unwraps a buy's WSOL into the SOL vault. Created at the user's expense
+    /// and closed again within the instruction, which refunds its rent, so
+    /// buys pass it and sells pass none.
+    #[account(
+        init,
+        payer = swap.user,
+        seeds = [b"swap_wsol", swap.pool.key().as_ref(), swap.user.key().as_ref()],
+        bump,
+        token::mint = wsol_mint,
+        token::authority = swap.pool,
+        token::token_program = wsol_token_program
+    )]
+    pub swap_wsol_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    pub wsol_token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Moves `amount` WSOL from the user into the ephemeral WSOL account and
+/// closes it into the SOL vault to unwrap it, then refunds the account's rent
+/// to the user. The SOL vault ends up `amount` lamports richer.
+fn unwrap_into_sol_vault(accounts: &SwapWsol, amount: u64) -> Result<()> {
+    let swap_wsol_account = accounts
+        .swap_wsol_account
+        .as_ref()
+        .ok_or(LiquidityPoolError::InvalidSwapParams)?
+        .to_account_info();
+    let token_program = accounts.wsol_token_program.to_account_info();
+    let sol_vault = accounts.swap.sol_vault.to_account_info();
+    let pool = &accounts.swap.pool;
+    let rent = swap_wsol_account.lamports();
+
+    token::transfer_checked(
+        CpiContext::new(
+            token_program.clone(),
+            TransferChecked {
+                from: accounts.user_wsol_account.to_account_info(),
+                to: swap_wsol_account.clone(),
+                mint: accounts.wsol_mint.to_account_info(),
+                authority: accounts.swap.user.to_account_info(),
+            },
+        ),
+        amount,
+        accounts.wsol_mint.decimals,
+    )?;
+
+    token::close_account(CpiContext::new_with_signer(
+        token_program,
+        CloseAccount {
+            account: swap_wsol_account,
+            destination: sol_vault.clone(),
+            authority: pool.to_account_info(),
+        },
+        &[&pool.signer_seeds()],
+    ))?;
+
+    transfer_from_sol_vault(
+        &accounts.system_program.to_account_info(),
+        &sol_vault,
+        &accounts.swap.user.to_account_info(),
+        &pool.key(),
+        pool.sol_vault_bump,
+        rent,
+    )
+}
+
+/// Credits the lamports a sell paid into the user's WSOL account to its token
+/// balance.
+fn sync_user_wsol(accounts: &SwapWsol) -> Result<()> {
+    token::sync_native(CpiContext::new(
+        accounts.wsol_token_program.to_account_info(),
+        SyncNative {
+            account: accounts.user_wsol_account.to_account_info(),
+        },
+    ))
+}
+
+pub fn swap_wsol_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, SwapWsol<'info>>,
+    direction: SwapDirection,
+    mode: SwapMode,
+) -> Result<()> {
+    let accounts = ctx.accounts;
+
+    require!(accounts.swap.pool.is_native_quote(), LiquidityPoolError::UnsupportedQuoteMint);
+
+    let user_wsol_account = accounts.user_wsol_account.to_account_info();
+
+    match direction {
+        SwapDirection::Buy => {
+            // Unwrap exactly what the swap will charge into the SOL vault;
+            // the swap then only moves the fee on to the fee vault.
+            let mint_info = accounts.swap.token_mint.to_account_info();
+            let quote = quote_swap(
+                &accounts.swap.pool,
+                accounts.swap.token_mint.decimals,
+                direction,
+                mode,
+                &CurrentTransferFee(&mint_info),
+            )?;
+            unwrap_into_sol_vault(accounts, quote.amount_in(direction))?;
+
+            let swap = execute_swap(
+                &mut accounts.swap,
+                ctx.remaining_accounts,
+                direction,
+                mode,
+                SolSettlement::Account(&user_wsol_account),
+            )?;
+            require!(swap.sol_amount == quote.sol_amount, LiquidityPoolError::InvalidSwapParams);
+        },
+        SwapDirection::Sell => {
+            require!(accounts.swap_wsol_account.is_none(), LiquidityPoolError::InvalidSwapParams);
+
+            execute_swap(
+                &mut accounts.swap,
+                ctx.remaining_accounts,
+                direction,
+                mode,
+                SolSettlement::Account(&user_wsol_account),
+            )?;
+
+            sync_user_wsol(accounts)?;
+        },
+    }
+
+    Ok(())
+}
@@ -11,6 +11,8 @@ pub mod events;
 pub mod utils;
 
 use instructions::*;
+use state::{CompletionThreshold, CurveKind, GlobalConfigParams, PoolInfo, SwapDirection, SwapMode, SwapQuote};
+use utils::math::Price;
 
 #[cfg(not(feature = "no-entrypoint"))]
 security_txt! {
@@ -34,48 +36,133 @@ pub mod liquidity_pool {
 
     pub fn create_pool(
         ctx: Context<CreatePool>,
+        pool_index: u8,
         virtual_token_reserve: Option<u64>,
         virtual_sol_reserve: Option<u64>,
+        curve: CurveKind,
+        completion_threshold: CompletionThreshold,
     ) -> Result<()> {
-        instructions::create_pool::handler(ctx, virtual_token_reserve, virtual_sol_reserve)
+        instructions::create_pool::handler(
+            ctx,
+            pool_index,
+            virtual_token_reserve,
+            virtual_sol_reserve,
+            curve,
+            completion_threshold,
+        )
     }
 
     pub fn create_pool_token_account(ctx: Context<CreatePoolTokenAccount>) -> Result<()> {
         instructions::create_pool_token_account::create_pool_token_account_handler(ctx)
     }
 
-    pub fn add_liquidity(
-        ctx: Context<AddLiquidity>,
-        sol_amount: u64,
-        token_amount: u64,
+    pub fn add_liquidity<'info>(
+        ctx: Context<'_, '_, '_, 'info, AddLiquidity<'info>>,
+        max_sol_amount: u64,
+        max_token_amount: u64,
+        min_lp_amount: u64,
         token_decimals: u8,
     ) -> Result<()> {
-        instructions::add_liquidity::add_liquidity_handler(ctx, sol_amount, token_amount, token_decimals)
+        instructions::add_liquidity::add_liquidity_handler(ctx, max_sol_amount, max_token_amount, min_lp_amount, token_decimals)
     }
 
-    pub fn remove_liquidity(
-        ctx: Context<RemoveLiquidity>,
+    pub fn remove_liquidity<'info>(
+        ctx: Context<'_, '_, '_, 'info, RemoveLiquidity<'info>>,
+        lp_amount: u64,
+        min_sol_amount: u64,
+        min_token_amount: u64,
         token_decimals: u8,
     ) -> Result<()> {
-        instructions::remove_liquidity::remove_liquidity_handler(ctx, token_decimals)
+        instructions::remove_liquidity::remove_liquidity_handler(ctx, lp_amount, min_sol_amount, min_token_amount, token_decimals)
     }
 
-    pub fn swap_tokens_for_sol(
-        ctx: Context<SwapTokensForSol>,
-        token_amount: u64,
-        sol_output: u64,
+    pub fn swap<'info>(
+        ctx: Context<'_, '_, '_, 'info, Swap<'info>>,
+        direction: SwapDirection,
+        mode: SwapMode,
         token_decimals: u8,
     ) -> Result<()> {
-        instructions::swap::swap_tokens_for_sol_handler(ctx, token_amount, sol_output, token_decimals)
+        instructions::swap::swap_handler(ctx, direction, mode, token_decimals)
     }
 
-    pub fn swap_sol_for_tokens(
-        ctx: Context<SwapSolForTokens>,
-        sol_amount: u64,
-        token_output: u64,
-        token_decimals: u8,
+    pub fn add_liquidity_v2<'info>(
+        ctx: Context<'_, '_, '_, 'info, AddLiquidity<'info>>,
+        max_sol_amount: u64,
+        max_token_amount: u64,
+        min_lp_amount: u64,
+    ) -> Result<()> {
+        instructions::add_liquidity::add_liquidity_v2_handler(ctx, max_sol_amount, max_token_amount, min_lp_amount)
+    }
+
+    pub fn remove_liquidity_v2<'info>(
+        ctx: Context<'_, '_, '_, 'info, RemoveLiquidity<'info>>,
+        lp_amount: u64,
+        min_sol_amount: u64,
+        min_token_amount: u64,
+    ) -> Result<()> {
+        instructions::remove_liquidity::remove_liquidity_v2_handler(ctx, lp_amount, min_sol_amount, min_token_amount)
+    }
+
+    pub fn swap_v2<'info>(
+        ctx: Context<'_, '_, '_, 'info, Swap<'info>>,
+        direction: SwapDirection,
+        mode: SwapMode,
+    ) -> Result<()> {
+        instructions::swap::swap_v2_handler(ctx, direction, mode)
+    }
+
+    pub fn swap_wsol<'info>(
+        ctx: Context<'_, '_, '_, 'info, SwapWsol<'info>>,
+        direction: SwapDirection,
+        mode: SwapMode,
+    ) -> Result<()> {
+        instructions::swap_wsol::swap_wsol_handler(ctx, direction, mode)
+    }
+
+    pub fn swap_via_sol<'info>(
+        ctx: Context<'_, '_, '_, 'info, SwapViaSol<'info>>,
+        amount_in: u64,
+        min_amount_out: u64,
     ) -> Result<()> {
-        instructions::swap::swap_sol_for_tokens_handler(ctx, sol_amount, token_output, token_decimals)
+        instructions::swap_via_sol::swap_via_sol_handler(ctx, amount_in, min_amount_out)
+    }
+
+    pub fn migrate<'info>(ctx: Context<'_, '_, '_, 'info, Migrate<'info>>) -> Result<()> {
+        instructions::migrate::migrate_handler(ctx)
+    }
+
+    pub fn sync(ctx: Context<SyncPool>) -> Result<()> {
+        instructions::reconcile::sync_handler(ctx)
+    }
+
+    pub fn skim<'info>(ctx: Context<'_, '_, '_, 'info, SkimPool<'info>>) -> Result<()> {
+        instructions::reconcile::skim_handler(ctx)
+    }
+
+    pub fn amm_add_liquidity<'info>(
+        ctx: Context<'_, '_, '_, 'info, AmmLiquidity<'info>>,
+        max_sol_amount: u64,
+        max_token_amount: u64,
+        min_lp_amount: u64,
+    ) -> Result<()> {
+        instructions::amm_liquidity::amm_add_liquidity_handler(ctx, max_sol_amount, max_token_amount, min_lp_amount)
+    }
+
+    pub fn amm_remove_liquidity<'info>(
+        ctx: Context<'_, '_, '_, 'info, AmmLiquidity<'info>>,
+        lp_amount: u64,
+        min_sol_amount: u64,
+        min_token_amount: u64,
+    ) -> Result<()> {
+        instructions::amm_liquidity::amm_remove_liquidity_handler(ctx, lp_amount, min_sol_amount, min_token_amount)
+    }
+
+    pub fn amm_swap<'info>(
+        ctx: Context<'_, '_, '_, 'info, AmmSwap<'info>>,
+        direction: SwapDirection,
+        mode: SwapMode,
+    ) -> Result<()> {
+        instructions::amm_swap::amm_swap_handler(ctx, direction, mode)
     }
 
     pub fn update_exchange_rate(
@@ -85,23 +172,70 @@ pub mod liquidity_pool {
         instructions::admin::update_exchange_rate_handler(ctx, new_rate)
     }
 
+    pub fn set_fee(ctx: Context<SetFee>, fee_basis_points: u16) -> Result<()> {
+        instructions::admin::set_fee_handler(ctx, fee_basis_points)
+    }
+
+    pub fn claim_fees(ctx: Context<ClaimFees>) -> Result<()> {
+        instructions::claim_fees::claim_fees_handler(ctx)
+    }
+
+    pub fn claim_protocol_fees(ctx: Context<ClaimProtocolFees>) -> Result<()> {
+        instructions::claim_fees::claim_protocol_fees_handler(ctx)
+    }
+
+    pub fn initialize_global_config(
+        ctx: Context<InitializeGlobalConfig>,
+        params: GlobalConfigParams,
+    ) -> Result<()> {
+        instructions::global_config::initialize_global_config_handler(ctx, params)
+    }
+
+    pub fn update_global_config(
+        ctx: Context<UpdateGlobalConfig>,
+        new_admin: Option<Pubkey>,
+        params: GlobalConfigParams,
+    ) -> Result<()> {
+        instructions::global_config::update_global_config_handler(ctx, new_admin, params)
+    }
+
+    pub fn set_paused(ctx: Context<UpdateGlobalConfig>, paused: bool) -> Result<()> {
+        instructions::global_config::set_paused_handler(ctx, paused)
+    }
+
+    pub fn add_allowed_creator(ctx: Context<AddAllowedCreator>, creator: Pubkey) -> Result<()> {
+        instructions::global_config::add_allowed_creator_handler(ctx, creator)
+    }
+
+    pub fn remove_allowed_creator(ctx: Context<RemoveAllowedCreator>) -> Result<()> {
+        instructions::global_config::remove_allowed_creator_handler(ctx)
+    }
+
+    pub fn init_fee_account(ctx: Context<InitFeeAccount>, recipient: Pubkey) -> Result<()> {
+        instructions::init_fee_account::init_fee_account_handler(ctx, recipient)
+    }
+
     pub fn toggle_pool(ctx: Context<TogglePool>) -> Result<()> {
         instructions::admin::toggle_pool_handler(ctx)
     }
 
-    pub fn close_pool(ctx: Context<ClosePool>) -> Result<()> {
+    pub fn close_pool<'info>(ctx: Context<'_, '_, '_, 'info, ClosePool<'info>>) -> Result<()> {
         instructions::admin::close_pool_handler(ctx)
     }
 
-    pub fn get_pool_info(ctx: Context<GetPoolInfo>) -> Result<()> {
+    pub fn get_pool_info(ctx: Context<GetPoolInfo>) -> Result<PoolInfo> {
         instructions::query::get_pool_info_handler(ctx)
     }
 
     pub fn calculate_swap(
         ctx: Context<CalculateSwap>,
-        input_amount: u64,
-        token_to_sol: bool,
-    ) -> Result<()> {
-        instructions::query::calculate_swap_handler(ctx, input_amount, token_to_sol)
+        direction: SwapDirection,
+        mode: SwapMode,
+    ) -> Result<SwapQuote> {
+        instructions::query::calculate_swap_handler(ctx, direction, mode)
+    }
+
+    pub fn get_twap(ctx: Context<GetTwap>, window: u32) -> Result<Price> {
+        instructions::query::get_twap_handler(ctx, window)
     }
 }
\ No newline at end of file
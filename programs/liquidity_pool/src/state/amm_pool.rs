@@ -0,0 +1,56 @@
+// programs/liquidity_pool/src/state/amm_pool.rs
+use anchor_lang::prelude::*;
+use crate::state::{AMM_POOL_SEED, NATIVE_QUOTE_MINT};
+
+/// Graduated constant-product pool created when a launch curve migrates.
+/// Reserves are tracked explicitly in raw token and quote units. For SOL pools
+/// the SOL reserve is held in the system-owned `amm_sol_vault` above its
+/// rent-exempt minimum; other quote mints are held in the `amm_quote` vault.
+#[account]
+pub struct AmmPool {
+    pub token_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    /// `pool_index` of the launch pool it graduated from.
+    pub pool_index: u8,
+    pub lp_mint: Pubkey,
+    pub launch_pool: Pubkey,
+    pub sol_reserve: u64,
+    pub token_reserve: u64,
+    pub locked_lp_amount: u64,
+    pub fee_basis_points: u16,
+    pub bump: u8,
+    pub token_vault_bump: u8,
+    pub lp_mint_bump: u8,
+    pub quote_vault_bump: u8,
+    pub sol_vault_bump: u8,
+    pub created_at: i64,
+    pub total_volume_sol: u64,
+    pub total_volume_token: u64,
+    pub trade_count: u64,
+    pub last_trade_timestamp: i64,
+}
+
+impl AmmPool {
+    pub const SPACE: usize = 8 + 32 + 32 + 1 + 32 + 32 + 8 + 8 + 8 + 2 + 1 + 1 + 1 + 1 + 1 + 8 + 8 + 8 + 8 + 8 + 64;
+
+    pub fn is_native_quote(&self) -> bool {
+        self.quote_mint == NATIVE_QUOTE_MINT
+    }
+
+    pub fn signer_seeds(&self) -> [&[u8]; 5] {
+        [
+            AMM_POOL_SEED,
+            self.token_mint.as_ref(),
+            self.quote_mint.as_ref(),
+            std::slice::from_ref(&self.pool_index),
+            std::slice::from_ref(&self.bump),
+        ]
+    }
+
+    pub fn update_trade_stats(&mut self, sol_amount: u64, token_amount: u64, timestamp: i64) {
+        self.total_volume_sol = self.total_volume_sol.saturating_add(sol_amount);
+        self.total_volume_token = self.total_volume_token.saturating_add(token_amount);
+        self.trade_count = self.trade_count.saturating_add(1);
+        self.last_trade_timestamp = timestamp;
+    }
+}
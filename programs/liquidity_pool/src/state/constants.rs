@@ -1,13 +1,50 @@
 // programs/liquidity_pool/src/state/constants.rs
+use anchor_lang::prelude::Pubkey;
+
 pub const MAX_FEE_BASIS_POINTS: u16 = 1000;
+pub const FEE_SHARE_TOTAL_BPS: u16 = 10000;
 pub const MIN_EXCHANGE_RATE: u64 = 1;
 pub const MAX_EXCHANGE_RATE: u64 = u64::MAX;
 pub const MIN_SWAP_AMOUNT: u64 = 1;
+pub const MAX_TOKEN_DECIMALS: u8 = 18;
+pub const NORMALIZED_UNITS_PER_TOKEN: u128 = 1_000_000_000;
+pub const PRICE_SCALE: u128 = 1_000_000_000_000;
 pub const POOL_SEED: &[u8] = b"pool";
+pub const POOL_TOKEN_SEED: &[u8] = b"pool_token";
+pub const POOL_REGISTRY_SEED: &[u8] = b"pool_registry";
+pub const MINT_REGISTRY_SEED: &[u8] = b"mint_registry";
+/// One registry entry per possible `pool_index`.
+pub const MAX_POOLS_PER_PAIR: usize = 256;
+pub const QUOTE_VAULT_SEED: &[u8] = b"quote_vault";
+pub const FEE_VAULT_SEED: &[u8] = b"fee_vault";
+pub const GLOBAL_CONFIG_SEED: &[u8] = b"global_config";
+pub const CREATOR_ALLOWLIST_SEED: &[u8] = b"creator_allowlist";
+pub const FEE_ACCOUNT_SEED: &[u8] = b"fee_account";
+/// Takes the recipient's place in the seeds of a pool's protocol fee account,
+/// so the account stays put when the treasury changes.
+pub const PROTOCOL_FEE_ACCOUNT_SEED: &[u8] = b"protocol";
+pub const SOL_VAULT_SEED: &[u8] = b"sol_vault";
+pub const SWAP_WSOL_SEED: &[u8] = b"swap_wsol";
+pub const ORACLE_SEED: &[u8] = b"oracle";
+pub const ORACLE_CAPACITY: usize = 64;
+pub const LP_MINT_SEED: &[u8] = b"lp_mint";
+pub const LP_LOCK_SEED: &[u8] = b"lp_lock";
+pub const AMM_POOL_SEED: &[u8] = b"amm_pool";
+pub const AMM_TOKEN_VAULT_SEED: &[u8] = b"amm_token";
+pub const AMM_QUOTE_VAULT_SEED: &[u8] = b"amm_quote";
+pub const AMM_SOL_VAULT_SEED: &[u8] = b"amm_sol_vault";
+pub const AMM_LP_MINT_SEED: &[u8] = b"amm_lp_mint";
+pub const AMM_LP_LOCK_SEED: &[u8] = b"amm_lp_lock";
+/// Quote mint of pools that trade against native SOL. Their quote reserve is
+/// held as lamports rather than in a quote vault token account.
+pub const NATIVE_QUOTE_MINT: Pubkey = anchor_spl::token::spl_token::native_mint::ID;
+pub const LP_MINT_DECIMALS: u8 = 9;
+pub const MINIMUM_LIQUIDITY: u64 = 1_000;
 pub const DEX_NAME: &str = "Liquidity Pool";
 pub const PROGRAM_NAME: &str = "Liquidity Pool";
 pub const DEX_TYPE: &str = "AMM";
 pub const POOL_TYPE: &str = "AMM";
+pub const AMM_POOL_TYPE: &str = "CPMM";
 pub const TRANSACTION_TYPE: &str = "swap";
 pub const PROGRAM_VERSION: &str = "1.0.0";
 
@@ -0,0 +1,33 @@
+// programs/liquidity_pool/src/state/curve.rs
+use anchor_lang::prelude::*;
+
+/// Pricing curve a pool trades on. Prices are lamports per whole token scaled by
+/// `PRICE_SCALE`; token quantities are normalized to 9 decimals.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CurveKind {
+    /// x * y = k over virtual plus real reserves.
+    ConstantProduct,
+    /// price = base_price + slope * tokens_sold, with `slope` per whole token.
+    Linear { base_price: u64, slope: u64 },
+    /// price = base_price * e^(growth_rate * tokens_sold), with `growth_rate`
+    /// per whole token scaled by 1e18.
+    Exponential { base_price: u64, growth_rate: u64 },
+}
+
+impl CurveKind {
+    pub const SPACE: usize = 1 + 8 + 8;
+}
+
+/// Point at which a launch curve stops trading and becomes eligible for migration.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CompletionThreshold {
+    None,
+    /// Real quote reserve held by the pool (lamports for SOL pools).
+    SolRaised(u64),
+    /// Normalized token units sold from the curve.
+    TokensSold(u64),
+}
+
+impl CompletionThreshold {
+    pub const SPACE: usize = 1 + 8;
+}
@@ -0,0 +1,19 @@
+// programs/liquidity_pool/src/state/fee_account.rs
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct FeeAccount {
+    pub pool: Pubkey,
+    pub recipient: Pubkey,
+    pub bump: u8,
+    pub total_earned: u64,
+    pub total_claimed: u64,
+}
+
+impl FeeAccount {
+    pub const SPACE: usize = 8 + 32 + 32 + 1 + 8 + 8;
+
+    pub fn claimable(&self) -> u64 {
+        self.total_earned.saturating_sub(self.total_claimed)
+    }
+}
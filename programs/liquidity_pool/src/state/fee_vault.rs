@@ -0,0 +1,18 @@
+// programs/liquidity_pool/src/state/fee_vault.rs
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct FeeVault {
+    pub pool: Pubkey,
+    pub bump: u8,
+    pub total_collected: u64,
+    pub total_claimed: u64,
+}
+
+impl FeeVault {
+    pub const SPACE: usize = 8 + 32 + 1 + 8 + 8;
+
+    pub fn unclaimed(&self) -> u64 {
+        self.total_collected.saturating_sub(self.total_claimed)
+    }
+}
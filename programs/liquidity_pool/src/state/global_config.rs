@@ -0,0 +1,54 @@
+// programs/liquidity_pool/src/state/global_config.rs
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PoolCreationPolicy {
+    Permissionless,
+    Allowlisted,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GlobalConfigParams {
+    pub treasury: Pubkey,
+    pub protocol_share_bps: u16,
+    pub creator_share_bps: u16,
+    pub referrer_share_bps: u16,
+    pub default_fee_basis_points: u16,
+    pub pool_creation_policy: PoolCreationPolicy,
+}
+
+#[account]
+pub struct GlobalConfig {
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+    pub protocol_share_bps: u16,
+    pub creator_share_bps: u16,
+    pub referrer_share_bps: u16,
+    pub default_fee_basis_points: u16,
+    pub pool_creation_policy: PoolCreationPolicy,
+    pub is_paused: bool,
+    pub bump: u8,
+}
+
+impl GlobalConfig {
+    pub const SPACE: usize = 8 + 32 + 32 + 2 + 2 + 2 + 2 + 1 + 1 + 1;
+
+    pub fn apply(&mut self, params: &GlobalConfigParams) {
+        self.treasury = params.treasury;
+        self.protocol_share_bps = params.protocol_share_bps;
+        self.creator_share_bps = params.creator_share_bps;
+        self.referrer_share_bps = params.referrer_share_bps;
+        self.default_fee_basis_points = params.default_fee_basis_points;
+        self.pool_creation_policy = params.pool_creation_policy;
+    }
+}
+
+#[account]
+pub struct CreatorAllowlistEntry {
+    pub creator: Pubkey,
+    pub bump: u8,
+}
+
+impl CreatorAllowlistEntry {
+    pub const SPACE: usize = 8 + 32 + 1;
+}
@@ -1,6 +1,24 @@
 // programs/liquidity_pool/src/state/mod.rs
 pub mod pool;
+pub mod pool_registry;
+pub mod curve;
+pub mod amm_pool;
+pub mod swap;
+pub mod fee_vault;
+pub mod global_config;
+pub mod fee_account;
+pub mod oracle;
+pub mod query;
 pub mod constants;
 
 pub use pool::*;
-pub use constants::*;
\ No newline at end of file
+pub use pool_registry::*;
+pub use curve::*;
+pub use amm_pool::*;
+pub use swap::*;
+pub use fee_vault::*;
+pub use global_config::*;
+pub use fee_account::*;
+pub use oracle::*;
+pub use query::*;
+pub use constants::*;
@@ -0,0 +1,58 @@
+// programs/liquidity_pool/src/state/oracle.rs
+use anchor_lang::prelude::*;
+use crate::state::ORACLE_CAPACITY;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Observation {
+    pub timestamp: i64,
+    /// `LiquidityPool::price_cumulative` at `timestamp`.
+    pub price_cumulative: u128,
+}
+
+impl Observation {
+    pub const SPACE: usize = 8 + 16;
+}
+
+/// Ring buffer of price accumulator observations, written at most once per
+/// second by swaps. A TWAP is available for any window reaching back no
+/// further than the oldest retained observation.
+#[account]
+pub struct PoolOracle {
+    pub pool: Pubkey,
+    pub bump: u8,
+    /// Slot of the most recent observation.
+    pub index: u16,
+    pub observations: Vec<Observation>,
+}
+
+impl PoolOracle {
+    pub const SPACE: usize = 8 + 32 + 1 + 2 + 4 + Observation::SPACE * ORACLE_CAPACITY;
+
+    pub fn latest(&self) -> Option<&Observation> {
+        self.observations.get(self.index as usize)
+    }
+
+    pub fn oldest(&self) -> Option<&Observation> {
+        if self.observations.len() < ORACLE_CAPACITY {
+            self.observations.first()
+        } else {
+            self.observations.get((self.index as usize + 1) % ORACLE_CAPACITY)
+        }
+    }
+
+    pub fn record(&mut self, timestamp: i64, price_cumulative: u128) {
+        if matches!(self.latest(), Some(latest) if latest.timestamp >= timestamp) {
+            return;
+        }
+
+        let observation = Observation { timestamp, price_cumulative };
+
+        if self.observations.len() < ORACLE_CAPACITY {
+            self.observations.push(observation);
+            self.index = (self.observations.len() - 1) as u16;
+        } else {
+            self.index = ((self.index as usize + 1) % ORACLE_CAPACITY) as u16;
+            self.observations[self.index as usize] = observation;
+        }
+    }
+}
@@ -1,13 +1,48 @@
 // programs/liquidity_pool/src/state/pool.rs
 use anchor_lang::prelude::*;
+use crate::state::{CompletionThreshold, CurveKind, NATIVE_QUOTE_MINT, POOL_SEED};
+use crate::utils::curve::PoolCurve;
+use crate::utils::math::{get_effective_pool_reserves, normalize_token_amount, Price};
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PoolStatus {
+    Trading,
+    Completed,
+    Migrated,
+}
+
+impl PoolStatus {
+    pub const SPACE: usize = 1;
+}
+
+/// Curves price against effective reserves, `virtual_* + real_*`. Virtual
+/// reserves exist only on constant-product pools and are shifted by deposits
+/// and withdrawals so that liquidity changes never move the price. Real
+/// reserves are what the pool actually holds: `real_sol` is the `sol_vault`
+/// balance above its rent-exempt minimum and `real_token` is the `pool_token`
+/// vault balance in raw token units. The vaults can only exceed them by
+/// direct donations.
+///
+/// The `sol` side is the pool's quote asset. For SOL pools (`quote_mint` is
+/// `NATIVE_QUOTE_MINT`) it is lamports in the `sol_vault`. Otherwise every
+/// `*_sol` amount and price is in base units of `quote_mint`, held in the
+/// `quote_vault` token account together with unclaimed fees.
+///
+/// `pool_index` tells apart pools of the same mints, so one token can have
+/// several pools against a quote mint, e.g. at different fee tiers.
 #[account]
 pub struct LiquidityPool {
     pub authority: Pubkey,
     pub token_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub pool_index: u8,
     pub exchange_rate: u64,
-    pub sol_reserve: u64,
-    pub token_reserve: u64,
+    pub virtual_sol: u64,
+    /// Normalized to 9 decimals.
+    pub virtual_token: u64,
+    pub real_sol: u64,
+    /// Raw token units.
+    pub real_token: u64,
     pub is_active: bool,
     pub created_at: i64,
     pub fee_basis_points: u16,
@@ -16,21 +51,81 @@ pub struct LiquidityPool {
     pub total_volume_token: u64,
     pub trade_count: u64,
     pub last_trade_timestamp: i64,
-    pub current_price: f64,
+    pub current_price: Price,
+    pub curve: CurveKind,
+    pub tokens_sold: u64,
+    pub status: PoolStatus,
+    pub completion_threshold: CompletionThreshold,
+    pub sol_vault_bump: u8,
+    /// Sum of spot price (lamports per whole token, scaled by `PRICE_SCALE`)
+    /// times seconds it was held. Wraps on overflow; consumers use differences.
+    pub price_cumulative: u128,
+    pub price_last_updated: i64,
 }
 
 impl LiquidityPool {
-    pub const SPACE: usize = 8 + 32 + 32 + 8 + 8 + 8 + 1 + 8 + 2 + 1 + 8 + 8 + 8 + 8 + 8 + 64;
+    pub const SPACE: usize = 8 + 32 + 32 + 32 + 1 + 8 + 8 + 8 + 8 + 8 + 1 + 8 + 2 + 1 + 8 + 8 + 8 + 8 + Price::SPACE + CurveKind::SPACE + 8
+        + PoolStatus::SPACE + CompletionThreshold::SPACE + 1 + 16 + 8 + 64;
+
+    pub fn is_native_quote(&self) -> bool {
+        self.quote_mint == NATIVE_QUOTE_MINT
+    }
+
+    /// Seeds of the pool PDA, for signing as the vault authority.
+    pub fn signer_seeds(&self) -> [&[u8]; 5] {
+        [
+            POOL_SEED,
+            self.token_mint.as_ref(),
+            self.quote_mint.as_ref(),
+            std::slice::from_ref(&self.pool_index),
+            std::slice::from_ref(&self.bump),
+        ]
+    }
+
+    /// Curve over the effective (virtual + real) reserves.
+    pub fn pricing_curve(&self, token_decimals: u8) -> Result<PoolCurve> {
+        let (effective_sol_reserve, effective_token_reserve) = get_effective_pool_reserves(
+            self.virtual_sol,
+            self.virtual_token,
+            self.real_sol,
+            normalize_token_amount(self.real_token, token_decimals)?,
+        )?;
+
+        Ok(PoolCurve::new(self.curve, self.tokens_sold, effective_sol_reserve, effective_token_reserve))
+    }
 
-    pub fn update_trade_stats(&mut self, sol_amount: u64, token_amount: u64, timestamp: i64) {
+    pub fn completion_progress_bps(&self) -> u16 {
+        let (progress, threshold) = match self.completion_threshold {
+            CompletionThreshold::None => return 0,
+            CompletionThreshold::SolRaised(threshold) => (self.real_sol, threshold),
+            CompletionThreshold::TokensSold(threshold) => (self.tokens_sold, threshold),
+        };
+
+        if threshold == 0 || progress >= threshold {
+            return 10_000;
+        }
+
+        (progress as u128 * 10_000 / threshold as u128) as u16
+    }
+
+    /// Accumulator extrapolated to `timestamp`, assuming `spot_price` has held
+    /// since the last update.
+    pub fn price_cumulative_at(&self, spot_price: u128, timestamp: i64) -> u128 {
+        let elapsed = timestamp.saturating_sub(self.price_last_updated).max(0) as u128;
+        self.price_cumulative.wrapping_add(spot_price.wrapping_mul(elapsed))
+    }
+
+    /// Call with the spot price before a trade moves it.
+    pub fn accumulate_price(&mut self, spot_price: u128, timestamp: i64) {
+        self.price_cumulative = self.price_cumulative_at(spot_price, timestamp);
+        self.price_last_updated = timestamp;
+    }
+
+    pub fn update_trade_stats(&mut self, sol_amount: u64, token_amount: u64, price: Price, timestamp: i64) {
         self.total_volume_sol = self.total_volume_sol.saturating_add(sol_amount);
         self.total_volume_token = self.total_volume_token.saturating_add(token_amount);
         self.trade_count = self.trade_count.saturating_add(1);
         self.last_trade_timestamp = timestamp;
-        self.current_price = if token_amount > 0 {
-            (sol_amount as f64) / (token_amount as f64)
-        } else {
-            0.0
-        };
+        self.current_price = price;
     }
 }
\ No newline at end of file
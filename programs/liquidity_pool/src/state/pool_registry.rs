@@ -0,0 +1,63 @@
+// programs/liquidity_pool/src/state/pool_registry.rs
+use anchor_lang::prelude::*;
+use crate::state::MAX_POOLS_PER_PAIR;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RegisteredPool {
+    pub pool: Pubkey,
+    pub pool_index: u8,
+}
+
+impl RegisteredPool {
+    pub const SPACE: usize = 32 + 1;
+}
+
+/// Open launch pools of one token against one quote mint, in creation order.
+/// Sized for every `pool_index`, so it can never fill up before the indexes
+/// run out. Graduated AMM pools are derived from their launch pool's mints and
+/// index.
+#[account]
+pub struct PoolRegistry {
+    pub token_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub bump: u8,
+    pub pools: Vec<RegisteredPool>,
+}
+
+impl PoolRegistry {
+    pub const SPACE: usize = 8 + 32 + 32 + 1 + 4 + RegisteredPool::SPACE * MAX_POOLS_PER_PAIR;
+
+    pub fn contains(&self, pool: &Pubkey) -> bool {
+        self.pools.iter().any(|registered| registered.pool == *pool)
+    }
+
+    pub fn remove(&mut self, pool: &Pubkey) {
+        self.pools.retain(|registered| registered.pool != *pool);
+    }
+}
+
+/// Quote mints a token has open launch pools against. Each one leads to the
+/// `PoolRegistry` of that pair, so clients can list every pool of a token
+/// without knowing its quote mints up front. Grows by one entry whenever a
+/// pool opens a new pair, and drops the entry when the pair's last pool
+/// closes.
+#[account]
+pub struct MintRegistry {
+    pub token_mint: Pubkey,
+    pub bump: u8,
+    pub quote_mints: Vec<Pubkey>,
+}
+
+impl MintRegistry {
+    pub const fn space(quote_mints: usize) -> usize {
+        8 + 32 + 1 + 4 + 32 * quote_mints
+    }
+
+    pub fn contains(&self, quote_mint: &Pubkey) -> bool {
+        self.quote_mints.contains(quote_mint)
+    }
+
+    pub fn remove(&mut self, quote_mint: &Pubkey) {
+        self.quote_mints.retain(|registered| registered != quote_mint);
+    }
+}
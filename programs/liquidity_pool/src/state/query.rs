@@ -0,0 +1,43 @@
+// programs/liquidity_pool/src/state/query.rs
+use anchor_lang::prelude::*;
+use crate::state::{CompletionThreshold, CurveKind, PoolStatus};
+use crate::utils::math::Price;
+
+/// Returned by `get_pool_info`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PoolInfo {
+    pub pool: Pubkey,
+    pub authority: Pubkey,
+    pub token_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub pool_index: u8,
+    pub curve: CurveKind,
+    pub status: PoolStatus,
+    pub is_active: bool,
+    pub virtual_sol: u64,
+    pub virtual_token: u64,
+    pub real_sol: u64,
+    pub real_token: u64,
+    pub spot_price: Price,
+    pub fee_basis_points: u16,
+    pub tokens_sold: u64,
+    pub completion_threshold: CompletionThreshold,
+    pub completion_progress_bps: u16,
+    /// Quote units, for the full mint supply at the spot price.
+    pub market_cap: u64,
+    pub total_volume_sol: u64,
+    pub total_volume_token: u64,
+    pub trade_count: u64,
+    pub created_at: i64,
+}
+
+/// Returned by `calculate_swap`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SwapQuote {
+    pub amount_in: u64,
+    pub amount_out: u64,
+    /// Quote units.
+    pub fee_amount: u64,
+    pub price_impact_bps: u64,
+    pub new_spot_price: Price,
+}
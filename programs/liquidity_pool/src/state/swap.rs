@@ -0,0 +1,18 @@
+// programs/liquidity_pool/src/state/swap.rs
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SwapDirection {
+    /// SOL in, tokens out.
+    Buy,
+    /// Tokens in, SOL out.
+    Sell,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SwapMode {
+    /// Spend exactly `amount_in`, receive at least `min_amount_out`.
+    ExactIn { amount_in: u64, min_amount_out: u64 },
+    /// Receive exactly `amount_out`, spend at most `max_amount_in`.
+    ExactOut { amount_out: u64, max_amount_in: u64 },
+}
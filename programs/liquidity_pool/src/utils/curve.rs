@@ -0,0 +1,528 @@
+// programs/liquidity_pool/src/utils/curve.rs
+use anchor_lang::prelude::*;
+use crate::error::LiquidityPoolError;
+use crate::state::{CurveKind, SwapDirection, NORMALIZED_UNITS_PER_TOKEN, PRICE_SCALE};
+use crate::utils::math::*;
+
+const WAD: u128 = 1_000_000_000_000_000_000;
+const LN2_WAD: u128 = 693_147_180_559_945_309;
+const MAX_EXPONENT_WAD: u128 = 40 * WAD;
+/// Integral curves track cumulative cost in lamports scaled by 1e30 so the
+/// integrals stay exact integers.
+const COST_SCALE: u128 = 1_000_000_000_000_000_000_000_000_000_000;
+const MAX_ROUNDING_STEPS: u32 = 8;
+
+/// Pricing interface shared by every curve kind. Token amounts are normalized
+/// to 9 decimals; prices are lamports per whole token scaled by `PRICE_SCALE`.
+pub trait Curve {
+    /// Amount out for an exact amount in: tokens for SOL on a buy, SOL for tokens on a sell.
+    fn quote_out(&self, direction: SwapDirection, amount_in: u64) -> Result<u64>;
+
+    /// Amount in required for an exact amount out: SOL on a buy, tokens on a sell.
+    fn quote_in(&self, direction: SwapDirection, amount_out: u64) -> Result<u64>;
+
+    fn spot_price(&self) -> Result<u128>;
+
+    /// Market cap in lamports of `supply` normalized token units at the spot price.
+    fn market_cap(&self, supply: u64) -> Result<u128> {
+        mul_div_u128(
+            self.spot_price()?,
+            supply as u128,
+            NORMALIZED_UNITS_PER_TOKEN * PRICE_SCALE,
+            false,
+        )
+    }
+}
+
+/// Curves defined by a price function of `tokens_sold`, priced by integrating
+/// that function. Implementors provide the cumulative cost `F(s)` and its inverse.
+pub trait IntegralCurve {
+    fn tokens_sold(&self) -> u64;
+
+    /// Cost in lamports (scaled by `COST_SCALE`) of selling the first `supply` units.
+    fn cumulative_cost(&self, supply: u128) -> Result<U256>;
+
+    /// Largest supply whose cumulative cost does not exceed `cost`.
+    fn supply_for_cost(&self, cost: U256) -> Result<u128>;
+
+    fn price_at(&self, supply: u128) -> Result<u128>;
+}
+
+impl<T: IntegralCurve> Curve for T {
+    fn quote_out(&self, direction: SwapDirection, amount_in: u64) -> Result<u64> {
+        let sold = self.tokens_sold() as u128;
+        let cost_now = self.cumulative_cost(sold)?;
+
+        match direction {
+            SwapDirection::Buy => {
+                let budget = U256::from(amount_in) * U256::from(COST_SCALE);
+                let target_supply = self.supply_for_cost(
+                    cost_now.checked_add(budget).ok_or(LiquidityPoolError::MathOverflow)?,
+                )?;
+                let mut tokens_out = target_supply.saturating_sub(sold);
+
+                for _ in 0..MAX_ROUNDING_STEPS {
+                    if tokens_out == 0 || self.cumulative_cost(sold + tokens_out)?.saturating_sub(cost_now) <= budget {
+                        break;
+                    }
+                    tokens_out -= 1;
+                }
+
+                u128_to_u64(tokens_out)
+            },
+            SwapDirection::Sell => {
+                let tokens_in = amount_in as u128;
+                require!(tokens_in <= sold, LiquidityPoolError::InsufficientLiquidity);
+
+                let proceeds = cost_now.saturating_sub(self.cumulative_cost(sold - tokens_in)?);
+                u128_to_u64(u256_to_u128(proceeds / U256::from(COST_SCALE))?)
+            },
+        }
+    }
+
+    fn quote_in(&self, direction: SwapDirection, amount_out: u64) -> Result<u64> {
+        let sold = self.tokens_sold() as u128;
+        let cost_now = self.cumulative_cost(sold)?;
+
+        match direction {
+            SwapDirection::Buy => {
+                let cost = self.cumulative_cost(sold + amount_out as u128)?.saturating_sub(cost_now);
+                let scale = U256::from(COST_SCALE);
+                let mut sol_in = cost / scale;
+                if sol_in * scale < cost {
+                    sol_in += U256::one();
+                }
+
+                u128_to_u64(u256_to_u128(sol_in)?)
+            },
+            SwapDirection::Sell => {
+                let proceeds = U256::from(amount_out) * U256::from(COST_SCALE);
+                require!(proceeds <= cost_now, LiquidityPoolError::InsufficientLiquidity);
+
+                let remaining_supply = self.supply_for_cost(cost_now - proceeds)?;
+                let mut tokens_in = sold.saturating_sub(remaining_supply);
+
+                for _ in 0..MAX_ROUNDING_STEPS {
+                    if tokens_in >= sold || cost_now.saturating_sub(self.cumulative_cost(sold - tokens_in)?) >= proceeds {
+                        break;
+                    }
+                    tokens_in += 1;
+                }
+
+                u128_to_u64(tokens_in)
+            },
+        }
+    }
+
+    fn spot_price(&self) -> Result<u128> {
+        self.price_at(self.tokens_sold() as u128)
+    }
+}
+
+pub struct ConstantProductCurve {
+    pub sol_reserve: u64,
+    pub token_reserve: u64,
+}
+
+impl Curve for ConstantProductCurve {
+    fn quote_out(&self, direction: SwapDirection, amount_in: u64) -> Result<u64> {
+        match direction {
+            SwapDirection::Buy => calculate_swap_output_amount(amount_in, self.sol_reserve, self.token_reserve),
+            SwapDirection::Sell => calculate_swap_output_amount(amount_in, self.token_reserve, self.sol_reserve),
+        }
+    }
+
+    fn quote_in(&self, direction: SwapDirection, amount_out: u64) -> Result<u64> {
+        match direction {
+            SwapDirection::Buy => calculate_swap_input_amount(amount_out, self.sol_reserve, self.token_reserve),
+            SwapDirection::Sell => calculate_swap_input_amount(amount_out, self.token_reserve, self.sol_reserve),
+        }
+    }
+
+    fn spot_price(&self) -> Result<u128> {
+        require!(self.token_reserve > 0, LiquidityPoolError::InsufficientLiquidity);
+        mul_div_u128(
+            self.sol_reserve as u128,
+            NORMALIZED_UNITS_PER_TOKEN * PRICE_SCALE,
+            self.token_reserve as u128,
+            false,
+        )
+    }
+}
+
+pub struct LinearCurve {
+    pub base_price: u64,
+    pub slope: u64,
+    pub tokens_sold: u64,
+}
+
+impl IntegralCurve for LinearCurve {
+    fn tokens_sold(&self) -> u64 {
+        self.tokens_sold
+    }
+
+    // F(s) = base_price * 1e9 * s + slope * s^2 / 2
+    fn cumulative_cost(&self, supply: u128) -> Result<U256> {
+        let supply = U256::from(supply);
+        let base_term = U256::from(self.base_price) * U256::from(NORMALIZED_UNITS_PER_TOKEN) * supply;
+        let slope_term = (supply * supply)
+            .checked_mul(U256::from(self.slope))
+            .ok_or(LiquidityPoolError::MathOverflow)?
+            / U256::from(2);
+        base_term.checked_add(slope_term).ok_or(LiquidityPoolError::MathOverflow.into())
+    }
+
+    // s = (sqrt(b^2 + 2 * slope * F) - b) / slope, with b = base_price * 1e9
+    fn supply_for_cost(&self, cost: U256) -> Result<u128> {
+        let b = U256::from(self.base_price) * U256::from(NORMALIZED_UNITS_PER_TOKEN);
+        if self.slope == 0 {
+            require!(!b.is_zero(), LiquidityPoolError::InvalidReserves);
+            return u256_to_u128(cost / b);
+        }
+
+        let slope = U256::from(self.slope);
+        let discriminant = (U256::from(2) * slope)
+            .checked_mul(cost)
+            .and_then(|slope_cost| slope_cost.checked_add(b * b))
+            .ok_or(LiquidityPoolError::MathOverflow)?;
+        u256_to_u128((discriminant.integer_sqrt() - b) / slope)
+    }
+
+    fn price_at(&self, supply: u128) -> Result<u128> {
+        let slope_term = mul_div_u128(self.slope as u128, supply, NORMALIZED_UNITS_PER_TOKEN, false)?;
+        (self.base_price as u128)
+            .checked_add(slope_term)
+            .ok_or(LiquidityPoolError::MathOverflow.into())
+    }
+}
+
+pub struct ExponentialCurve {
+    pub base_price: u64,
+    pub growth_rate: u64,
+    pub tokens_sold: u64,
+}
+
+impl ExponentialCurve {
+    fn exponent(&self, supply: u128) -> Result<u128> {
+        mul_div_u128(self.growth_rate as u128, supply, NORMALIZED_UNITS_PER_TOKEN, false)
+    }
+}
+
+impl IntegralCurve for ExponentialCurve {
+    fn tokens_sold(&self) -> u64 {
+        self.tokens_sold
+    }
+
+    // F(s) = base_price * (e^(k * s) - 1) * 1e18 / k
+    fn cumulative_cost(&self, supply: u128) -> Result<U256> {
+        require!(self.growth_rate > 0, LiquidityPoolError::InvalidReserves);
+        let growth = exp_wad(self.exponent(supply)?)?.saturating_sub(WAD);
+        Ok(U256::from(self.base_price) * U256::from(growth) * U256::from(WAD) / U256::from(self.growth_rate))
+    }
+
+    // s = ln(1 + F * k / (base_price * 1e18)) * 1e9 / k
+    fn supply_for_cost(&self, cost: U256) -> Result<u128> {
+        require!(self.base_price > 0 && self.growth_rate > 0, LiquidityPoolError::InvalidReserves);
+        let growth = cost.checked_mul(U256::from(self.growth_rate)).ok_or(LiquidityPoolError::MathOverflow)?
+            / (U256::from(self.base_price) * U256::from(WAD));
+        let exponent = ln_wad(WAD.checked_add(u256_to_u128(growth)?).ok_or(LiquidityPoolError::MathOverflow)?)?;
+        mul_div_u128(exponent, NORMALIZED_UNITS_PER_TOKEN, self.growth_rate as u128, false)
+    }
+
+    fn price_at(&self, supply: u128) -> Result<u128> {
+        mul_div_u128(self.base_price as u128, exp_wad(self.exponent(supply)?)?, WAD, false)
+    }
+}
+
+/// e^x for `x` scaled by 1e18, via e^x = 2^n * e^r with r < ln 2.
+pub fn exp_wad(x: u128) -> Result<u128> {
+    require!(x <= MAX_EXPONENT_WAD, LiquidityPoolError::MathOverflow);
+
+    let n = x / LN2_WAD;
+    let r = x - n * LN2_WAD;
+
+    let mut term = WAD;
+    let mut sum = WAD;
+    for i in 1..=32u128 {
+        term = term * r / WAD / i;
+        if term == 0 {
+            break;
+        }
+        sum += term;
+    }
+
+    sum.checked_shl(n as u32)
+        .filter(|v| v >> n == sum)
+        .ok_or(LiquidityPoolError::MathOverflow.into())
+}
+
+/// ln(y) for `y >= 1e18` scaled by 1e18, via ln(y) = n * ln 2 + 2 * atanh((m - 1) / (m + 1)).
+pub fn ln_wad(y: u128) -> Result<u128> {
+    require!(y >= WAD, LiquidityPoolError::MathOverflow);
+
+    let n = 127 - (y / WAD).leading_zeros();
+    let m = y >> n;
+
+    let z = (m - WAD) * WAD / (m + WAD);
+    let z_squared = z * z / WAD;
+
+    let mut term = z;
+    let mut sum = z;
+    let mut k = 3u128;
+    while k < 64 {
+        term = term * z_squared / WAD;
+        if term == 0 {
+            break;
+        }
+        sum += term / k;
+        k += 2;
+    }
+
+    Ok(n as u128 * LN2_WAD + 2 * sum)
+}
+
+/// Curve for a pool, built from its `CurveKind`, `tokens_sold` and effective reserves.
+pub enum PoolCurve {
+    ConstantProduct(ConstantProductCurve),
+    Linear(LinearCurve),
+    Exponential(ExponentialCurve),
+}
+
+impl PoolCurve {
+    pub fn new(kind: CurveKind, tokens_sold: u64, sol_reserve: u64, token_reserve: u64) -> Self {
+        match kind {
+            CurveKind::ConstantProduct => PoolCurve::ConstantProduct(ConstantProductCurve {
+                sol_reserve,
+                token_reserve,
+            }),
+            CurveKind::Linear { base_price, slope } => PoolCurve::Linear(LinearCurve {
+                base_price,
+                slope,
+                tokens_sold,
+            }),
+            CurveKind::Exponential { base_price, growth_rate } => PoolCurve::Exponential(ExponentialCurve {
+                base_price,
+                growth_rate,
+                tokens_sold,
+            }),
+        }
+    }
+
+    fn inner(&self) -> &dyn Curve {
+        match self {
+            PoolCurve::ConstantProduct(curve) => curve,
+            PoolCurve::Linear(curve) => curve,
+            PoolCurve::Exponential(curve) => curve,
+        }
+    }
+}
+
+impl Curve for PoolCurve {
+    fn quote_out(&self, direction: SwapDirection, amount_in: u64) -> Result<u64> {
+        self.inner().quote_out(direction, amount_in)
+    }
+
+    fn quote_in(&self, direction: SwapDirection, amount_out: u64) -> Result<u64> {
+        self.inner().quote_in(direction, amount_out)
+    }
+
+    fn spot_price(&self) -> Result<u128> {
+        self.inner().spot_price()
+    }
+
+    fn market_cap(&self, supply: u64) -> Result<u128> {
+        self.inner().market_cap(supply)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOKEN: u64 = NORMALIZED_UNITS_PER_TOKEN as u64;
+    const SOL: u64 = 1_000_000_000;
+    const SOLD: [u64; 4] = [0, 1, 1_000_000 * TOKEN, 500_000_000 * TOKEN];
+    const SOL_AMOUNTS: [u64; 5] = [1, 999, 10_000_000, SOL, 25 * SOL];
+    const TOKEN_AMOUNTS: [u64; 5] = [1, 999, TOKEN, 1_000 * TOKEN, 10_000_000 * TOKEN];
+
+    fn assert_close(actual: u128, expected: u128, tolerance: u128) {
+        assert!(
+            actual.abs_diff(expected) <= tolerance,
+            "{actual} differs from {expected} by more than {tolerance}"
+        );
+    }
+
+    // Both start at 28 lamports per token and end near 400 (linear) or 700
+    // (exponential) lamports per token after 800M tokens.
+    fn linear(tokens_sold: u64) -> LinearCurve {
+        LinearCurve { base_price: 28 * PRICE_SCALE as u64, slope: 465_000, tokens_sold }
+    }
+
+    fn exponential(tokens_sold: u64) -> ExponentialCurve {
+        ExponentialCurve { base_price: 28 * PRICE_SCALE as u64, growth_rate: (WAD / 250_000_000) as u64, tokens_sold }
+    }
+
+    fn assert_buys_never_favour_the_trader(name: &str, curve: impl IntegralCurve) {
+        let sold = curve.tokens_sold();
+        let cost_now = curve.cumulative_cost(sold as u128).unwrap();
+
+        for sol_in in SOL_AMOUNTS {
+            let tokens_out = curve.quote_out(SwapDirection::Buy, sol_in).unwrap();
+            let cost = curve.cumulative_cost(sold as u128 + tokens_out as u128).unwrap() - cost_now;
+            assert!(cost <= U256::from(sol_in) * U256::from(COST_SCALE), "{name}: buy for {sol_in} at {sold}");
+            assert!(curve.quote_in(SwapDirection::Buy, tokens_out).unwrap() <= sol_in, "{name}: buy for {sol_in} at {sold}");
+        }
+
+        for tokens_out in TOKEN_AMOUNTS {
+            let sol_in = curve.quote_in(SwapDirection::Buy, tokens_out).unwrap();
+            let cost = curve.cumulative_cost(sold as u128 + tokens_out as u128).unwrap() - cost_now;
+            assert!(U256::from(sol_in) * U256::from(COST_SCALE) >= cost, "{name}: buy of {tokens_out} at {sold}");
+            assert!(curve.quote_out(SwapDirection::Buy, sol_in).unwrap() >= tokens_out, "{name}: buy of {tokens_out} at {sold}");
+        }
+    }
+
+    fn assert_sells_never_favour_the_trader(name: &str, curve: impl IntegralCurve) {
+        let sold = curve.tokens_sold();
+        let cost_now = curve.cumulative_cost(sold as u128).unwrap();
+
+        for tokens_in in TOKEN_AMOUNTS {
+            let sol_out = curve.quote_out(SwapDirection::Sell, tokens_in).unwrap();
+            let proceeds = cost_now - curve.cumulative_cost((sold - tokens_in) as u128).unwrap();
+            assert!(U256::from(sol_out) * U256::from(COST_SCALE) <= proceeds, "{name}: sell of {tokens_in} at {sold}");
+        }
+
+        for sol_out in SOL_AMOUNTS {
+            let tokens_in = curve.quote_in(SwapDirection::Sell, sol_out).unwrap();
+            let proceeds = cost_now - curve.cumulative_cost((sold - tokens_in) as u128).unwrap();
+            assert!(proceeds >= U256::from(sol_out) * U256::from(COST_SCALE), "{name}: sell for {sol_out} at {sold}");
+            assert!(curve.quote_out(SwapDirection::Sell, tokens_in).unwrap() >= sol_out, "{name}: sell for {sol_out} at {sold}");
+        }
+    }
+
+    fn assert_monotonic(name: &str, curve: impl IntegralCurve) {
+        let sold = curve.tokens_sold();
+
+        let mut last_price = 0;
+        for supply in [0, 1, TOKEN, 1_000 * TOKEN, 1_000_000 * TOKEN, 900_000_000 * TOKEN] {
+            let price = curve.price_at(supply as u128).unwrap();
+            assert!(price >= last_price, "{name}: price at {supply}");
+            last_price = price;
+        }
+
+        let mut last_tokens = 0;
+        for sol_in in SOL_AMOUNTS {
+            let tokens_out = curve.quote_out(SwapDirection::Buy, sol_in).unwrap();
+            assert!(tokens_out >= last_tokens, "{name}: buy for {sol_in} at {sold}");
+            last_tokens = tokens_out;
+        }
+
+        let mut last_sol = 0;
+        for tokens_out in TOKEN_AMOUNTS {
+            let sol_in = curve.quote_in(SwapDirection::Buy, tokens_out).unwrap();
+            assert!(sol_in >= last_sol, "{name}: buy of {tokens_out} at {sold}");
+            last_sol = sol_in;
+        }
+    }
+
+    #[test]
+    fn exp_wad_matches_known_values() {
+        assert_eq!(exp_wad(0).unwrap(), WAD);
+        assert_close(exp_wad(WAD).unwrap(), 2_718_281_828_459_045_235, 100);
+        assert_close(exp_wad(LN2_WAD).unwrap(), 2 * WAD, 100);
+        assert_close(exp_wad(WAD / 2).unwrap(), 1_648_721_270_700_128_146, 100);
+        // e^40 to a relative error below 1e-13.
+        let e40 = 235_385_266_837_019_985_407_899_910_749_034_804;
+        assert_close(exp_wad(MAX_EXPONENT_WAD).unwrap(), e40, e40 / 10u128.pow(13));
+        assert!(exp_wad(MAX_EXPONENT_WAD + 1).is_err());
+    }
+
+    #[test]
+    fn ln_wad_matches_known_values() {
+        assert_eq!(ln_wad(WAD).unwrap(), 0);
+        assert_close(ln_wad(2 * WAD).unwrap(), LN2_WAD, 100);
+        assert_close(ln_wad(2_718_281_828_459_045_235).unwrap(), WAD, 100);
+        assert_close(ln_wad(10 * WAD).unwrap(), 2_302_585_092_994_045_684, 100);
+        assert_close(ln_wad(1_000_000 * WAD).unwrap(), 13_815_510_557_964_274_104, 100);
+        assert!(ln_wad(WAD - 1).is_err());
+    }
+
+    #[test]
+    fn ln_wad_inverts_exp_wad() {
+        for x in [1, WAD / 1_000, WAD / 3, WAD, 7 * WAD + 123, 25 * WAD, MAX_EXPONENT_WAD] {
+            assert_close(ln_wad(exp_wad(x).unwrap()).unwrap(), x, 1_000);
+        }
+    }
+
+    #[test]
+    fn integral_quotes_never_favour_the_trader() {
+        for sold in SOLD {
+            assert_buys_never_favour_the_trader("linear", linear(sold));
+            assert_buys_never_favour_the_trader("exponential", exponential(sold));
+        }
+
+        for sold in SOLD.into_iter().filter(|sold| *sold >= TOKEN_AMOUNTS[4]) {
+            assert_sells_never_favour_the_trader("linear", linear(sold));
+            assert_sells_never_favour_the_trader("exponential", exponential(sold));
+        }
+    }
+
+    #[test]
+    fn buying_and_selling_back_never_returns_more() {
+        for sold in SOLD {
+            for sol_in in SOL_AMOUNTS {
+                let tokens_out = linear(sold).quote_out(SwapDirection::Buy, sol_in).unwrap();
+                let sol_back = linear(sold + tokens_out).quote_out(SwapDirection::Sell, tokens_out).unwrap();
+                assert!(sol_back <= sol_in, "linear: {sol_in} at {sold}");
+
+                let tokens_out = exponential(sold).quote_out(SwapDirection::Buy, sol_in).unwrap();
+                let sol_back = exponential(sold + tokens_out).quote_out(SwapDirection::Sell, tokens_out).unwrap();
+                assert!(sol_back <= sol_in, "exponential: {sol_in} at {sold}");
+            }
+        }
+
+        let curve = ConstantProductCurve { sol_reserve: 30 * SOL, token_reserve: 1_073_000_000 * TOKEN };
+        for sol_in in SOL_AMOUNTS {
+            let tokens_out = curve.quote_out(SwapDirection::Buy, sol_in).unwrap();
+            let after = ConstantProductCurve {
+                sol_reserve: curve.sol_reserve + sol_in,
+                token_reserve: curve.token_reserve - tokens_out,
+            };
+            assert!(after.quote_out(SwapDirection::Sell, tokens_out).unwrap() <= sol_in, "constant product: {sol_in}");
+            assert!(curve.quote_in(SwapDirection::Buy, tokens_out).unwrap() <= sol_in, "constant product: {sol_in}");
+        }
+    }
+
+    #[test]
+    fn prices_and_quotes_are_monotonic() {
+        for sold in SOLD {
+            assert_monotonic("linear", linear(sold));
+            assert_monotonic("exponential", exponential(sold));
+        }
+
+        let tokens_out = linear(0).quote_out(SwapDirection::Buy, SOL).unwrap();
+        assert!(linear(tokens_out).spot_price().unwrap() > linear(0).spot_price().unwrap());
+        let tokens_out = exponential(0).quote_out(SwapDirection::Buy, SOL).unwrap();
+        assert!(exponential(tokens_out).spot_price().unwrap() > exponential(0).spot_price().unwrap());
+    }
+
+    #[test]
+    fn extreme_supplies_fail_instead_of_overflowing() {
+        let linear = LinearCurve { base_price: u64::MAX, slope: u64::MAX, tokens_sold: u64::MAX };
+        assert!(linear.spot_price().is_ok());
+        assert!(linear.quote_in(SwapDirection::Buy, u64::MAX).is_err());
+        assert!(linear.quote_out(SwapDirection::Sell, u64::MAX).is_err());
+        assert!(linear.quote_out(SwapDirection::Buy, u64::MAX).is_err());
+
+        // Exactly e^40 at the full supply: still priced, one more unit is not.
+        let whole_tokens = u64::MAX / TOKEN;
+        let growth_rate = (MAX_EXPONENT_WAD / whole_tokens as u128) as u64;
+        let exponential = ExponentialCurve { base_price: u64::MAX, growth_rate, tokens_sold: whole_tokens * TOKEN };
+        assert!(exponential.spot_price().is_ok());
+        assert!(exponential.quote_out(SwapDirection::Sell, u64::MAX).is_err());
+        assert!(exponential.quote_in(SwapDirection::Buy, TOKEN).is_err());
+
+        let steep = ExponentialCurve { base_price: 1, growth_rate: u64::MAX, tokens_sold: u64::MAX };
+        assert!(steep.spot_price().is_err());
+        assert!(steep.quote_out(SwapDirection::Buy, SOL).is_err());
+    }
+}
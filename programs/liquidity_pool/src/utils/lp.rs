@@ -0,0 +1,130 @@
+// programs/liquidity_pool/src/utils/lp.rs
+use anchor_lang::prelude::*;
+use crate::error::LiquidityPoolError;
+use crate::state::{NORMALIZED_UNITS_PER_TOKEN, PRICE_SCALE};
+use crate::utils::math::*;
+
+pub fn calculate_initial_lp_amount(sol_amount: u64, token_amount: u64) -> Result<u64> {
+    let product = U256::from(sol_amount) * U256::from(token_amount);
+    let lp_amount = u128_to_u64(u256_to_u128(product.integer_sqrt())?)?;
+    require!(lp_amount > 0, LiquidityPoolError::InsufficientLiquidity);
+    Ok(lp_amount)
+}
+
+/// (sol, tokens) that seed a constant-product pool at `spot_price` out of
+/// `max_sol` lamports and `max_tokens` normalized token units. One side is used
+/// in full and the other is cut down to match, rounded so the seeded price is
+/// never below `spot_price`.
+pub fn calculate_seed_amounts(max_sol: u64, max_tokens: u64, spot_price: u128) -> Result<(u64, u64)> {
+    require!(spot_price > 0, LiquidityPoolError::InsufficientLiquidity);
+
+    let matched_tokens = mul_div_u128(max_sol as u128, NORMALIZED_UNITS_PER_TOKEN * PRICE_SCALE, spot_price, false)?;
+    let (sol_amount, token_amount) = if matched_tokens <= max_tokens as u128 {
+        (max_sol, matched_tokens as u64)
+    } else {
+        let matched_sol = mul_div_u128(max_tokens as u128, spot_price, NORMALIZED_UNITS_PER_TOKEN * PRICE_SCALE, true)?;
+        (u128_to_u64(matched_sol.min(max_sol as u128))?, max_tokens)
+    };
+
+    require!(sol_amount > 0 && token_amount > 0, LiquidityPoolError::InsufficientLiquidity);
+    Ok((sol_amount, token_amount))
+}
+
+/// Shares minted for a deposit of at most `max_sol` / `max_tokens` at the current
+/// reserve ratio. Returns (lp_amount, sol_used, tokens_used); amounts used are
+/// rounded up so the pool never under-collects. A side with an empty reserve
+/// takes no deposit.
+pub fn calculate_deposit(
+    max_sol: u64,
+    max_tokens: u64,
+    sol_reserve: u64,
+    token_reserve: u64,
+    lp_supply: u64,
+) -> Result<(u64, u64, u64)> {
+    require!(
+        (sol_reserve > 0 || token_reserve > 0) && lp_supply > 0,
+        LiquidityPoolError::InsufficientLiquidity
+    );
+
+    let lp_from_sol = if sol_reserve > 0 {
+        mul_div_u128(max_sol as u128, lp_supply as u128, sol_reserve as u128, false)?
+    } else {
+        u128::MAX
+    };
+    let lp_from_tokens = if token_reserve > 0 {
+        mul_div_u128(max_tokens as u128, lp_supply as u128, token_reserve as u128, false)?
+    } else {
+        u128::MAX
+    };
+    let lp_amount = lp_from_sol.min(lp_from_tokens);
+
+    let sol_used = mul_div_u128(lp_amount, sol_reserve as u128, lp_supply as u128, true)?;
+    let tokens_used = mul_div_u128(lp_amount, token_reserve as u128, lp_supply as u128, true)?;
+
+    Ok((
+        u128_to_u64(lp_amount)?,
+        u128_to_u64(sol_used.min(max_sol as u128))?,
+        u128_to_u64(tokens_used.min(max_tokens as u128))?,
+    ))
+}
+
+/// Pro-rata (sol, tokens) paid out for burning `lp_amount` shares, rounded down.
+pub fn calculate_withdrawal(
+    lp_amount: u64,
+    lp_supply: u64,
+    sol_reserve: u64,
+    token_reserve: u64,
+) -> Result<(u64, u64)> {
+    require!(lp_amount > 0 && lp_amount <= lp_supply, LiquidityPoolError::InvalidSwapAmount);
+
+    let sol_amount = mul_div_u128(lp_amount as u128, sol_reserve as u128, lp_supply as u128, false)?;
+    let token_amount = mul_div_u128(lp_amount as u128, token_reserve as u128, lp_supply as u128, false)?;
+
+    Ok((u128_to_u64(sol_amount)?, u128_to_u64(token_amount)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::CurveKind;
+    use crate::utils::curve::{ConstantProductCurve, Curve, PoolCurve};
+
+    const TOKEN: u64 = NORMALIZED_UNITS_PER_TOKEN as u64;
+    const SOL: u64 = 1_000_000_000;
+
+    /// Curves at completion with 800M tokens sold: (curve, real_sol, real_token).
+    fn completed_curves() -> Vec<(PoolCurve, u64, u64)> {
+        let sold = 800_000_000 * TOKEN;
+        let (real_sol, real_token) = (85 * SOL, 206_900_000 * TOKEN);
+
+        vec![
+            (PoolCurve::new(CurveKind::ConstantProduct, sold, 30 * SOL + real_sol, 273_000_000 * TOKEN + real_token), real_sol, real_token),
+            (PoolCurve::new(CurveKind::Linear { base_price: 28 * PRICE_SCALE as u64, slope: 465_000 }, sold, 0, 0), real_sol, real_token),
+            (PoolCurve::new(CurveKind::Exponential { base_price: 28 * PRICE_SCALE as u64, growth_rate: 4_000_000_000 }, sold, 0, 0), real_sol, real_token),
+            // Few tokens left, so the SOL side is cut down instead.
+            (PoolCurve::new(CurveKind::Linear { base_price: 28 * PRICE_SCALE as u64, slope: 465_000 }, sold, 0, 0), real_sol, 1_000 * TOKEN),
+        ]
+    }
+
+    #[test]
+    fn seeded_amm_spot_price_matches_the_curve_at_completion() {
+        for (curve, real_sol, real_token) in completed_curves() {
+            let curve_price = curve.spot_price().unwrap();
+            let (sol_amount, token_amount) = calculate_seed_amounts(real_sol, real_token, curve_price).unwrap();
+
+            assert!(sol_amount <= real_sol && token_amount <= real_token);
+            assert!(sol_amount == real_sol || token_amount == real_token);
+
+            let amm_price = ConstantProductCurve { sol_reserve: sol_amount, token_reserve: token_amount }.spot_price().unwrap();
+            assert!(amm_price >= curve_price, "{amm_price} below {curve_price}");
+            assert!(amm_price - curve_price <= curve_price / 1_000_000_000, "{amm_price} differs from {curve_price}");
+        }
+    }
+
+    #[test]
+    fn seed_amounts_reject_an_empty_side() {
+        assert!(calculate_seed_amounts(0, 1_000 * TOKEN, 28 * PRICE_SCALE).is_err());
+        assert!(calculate_seed_amounts(SOL, 0, 28 * PRICE_SCALE).is_err());
+        assert!(calculate_seed_amounts(SOL, 1_000 * TOKEN, 0).is_err());
+    }
+}
@@ -1,6 +1,18 @@
 // programs/liquidity_pool/src/utils/math.rs
 use anchor_lang::prelude::*;
 use crate::error::LiquidityPoolError;
+use crate::state::constants::{MIN_EXCHANGE_RATE, NORMALIZED_UNITS_PER_TOKEN, PRICE_SCALE};
+
+pub use u256::U256;
+
+#[allow(clippy::all)]
+mod u256 {
+    use uint::construct_uint;
+
+    construct_uint! {
+        pub struct U256(4);
+    }
+}
 
 pub fn calculate_tokens_to_sol(token_amount: u64, exchange_rate: u64) -> Result<u64> {
     if exchange_rate == 0 {
@@ -53,6 +65,46 @@ pub fn calculate_fee(amount: u64, fee_basis_points: u16) -> Result<u64> {
         .ok_or(LiquidityPoolError::MathOverflow.into())
 }
 
+pub fn calculate_fee_on_net(net_amount: u64, fee_basis_points: u16) -> Result<u64> {
+    if fee_basis_points == 0 {
+        return Ok(0);
+    }
+
+    let denominator = 10000_u64
+        .checked_sub(fee_basis_points as u64)
+        .filter(|d| *d > 0)
+        .ok_or(LiquidityPoolError::FeeExceedsMaximum)?;
+
+    let gross_amount = (net_amount as u128)
+        .checked_mul(10000)
+        .and_then(|x| x.checked_add(denominator as u128 - 1))
+        .and_then(|x| x.checked_div(denominator as u128))
+        .ok_or(LiquidityPoolError::MathOverflow)?;
+
+    let gross_amount = u64::try_from(gross_amount).map_err(|_| LiquidityPoolError::MathOverflow)?;
+
+    safe_sub(gross_amount, net_amount)
+}
+
+/// Splits `fee_amount` into (protocol, creator, referrer) shares. Without a
+/// referrer its share goes to the protocol, as does any rounding dust.
+pub fn split_fee(
+    fee_amount: u64,
+    creator_share_bps: u16,
+    referrer_share_bps: u16,
+    has_referrer: bool,
+) -> Result<(u64, u64, u64)> {
+    let creator_fee = safe_mul_div(fee_amount, creator_share_bps as u64, 10000)?;
+    let referrer_fee = if has_referrer {
+        safe_mul_div(fee_amount, referrer_share_bps as u64, 10000)?
+    } else {
+        0
+    };
+    let protocol_fee = safe_sub(safe_sub(fee_amount, creator_fee)?, referrer_fee)?;
+
+    Ok((protocol_fee, creator_fee, referrer_fee))
+}
+
 pub fn safe_add(a: u64, b: u64) -> Result<u64> {
     a.checked_add(b).ok_or(LiquidityPoolError::MathOverflow.into())
 }
@@ -94,6 +146,122 @@ pub fn safe_mul_div(a: u64, b: u64, c: u64) -> Result<u64> {
     Ok(result as u64)
 }
 
+pub fn mul_div_u128(a: u128, b: u128, c: u128, round_up: bool) -> Result<u128> {
+    if c == 0 {
+        return Err(LiquidityPoolError::MathOverflow.into());
+    }
+
+    let numerator = U256::from(a) * U256::from(b);
+    let denominator = U256::from(c);
+    let mut result = numerator / denominator;
+    if round_up && result * denominator < numerator {
+        result += U256::one();
+    }
+
+    u256_to_u128(result)
+}
+
+pub fn u256_to_u128(value: U256) -> Result<u128> {
+    if value > U256::from(u128::MAX) {
+        return Err(LiquidityPoolError::MathOverflow.into());
+    }
+    Ok(value.as_u128())
+}
+
+pub fn u128_to_u64(value: u128) -> Result<u64> {
+    u64::try_from(value).map_err(|_| LiquidityPoolError::MathOverflow.into())
+}
+
+/// Fixed-point price in quote base units (lamports for SOL pools) per whole
+/// token, scaled by `PRICE_SCALE`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Price {
+    pub value: u128,
+}
+
+impl Price {
+    pub const SPACE: usize = 16;
+    pub const ZERO: Price = Price::new(0);
+
+    pub const fn new(value: u128) -> Self {
+        Price { value }
+    }
+
+    /// Price of `sol_amount` lamports paid for `token_amount` normalized token units.
+    pub fn from_amounts(sol_amount: u64, token_amount: u64) -> Result<Self> {
+        if token_amount == 0 {
+            return Ok(Self::ZERO);
+        }
+
+        Ok(Price::new(mul_div_u128(
+            sol_amount as u128,
+            NORMALIZED_UNITS_PER_TOKEN * PRICE_SCALE,
+            token_amount as u128,
+            false,
+        )?))
+    }
+
+    /// Quote tokens per whole token as an exact decimal string, for display.
+    /// `quote_decimals` is 9 for SOL pools.
+    pub fn to_ui_price(&self, quote_decimals: u8) -> Result<String> {
+        let (scale, digits) = Self::ui_scale(quote_decimals)?;
+        let whole = self.value / scale;
+        let fraction = self.value % scale;
+
+        if fraction == 0 {
+            return Ok(whole.to_string());
+        }
+
+        let fraction = format!("{fraction:0width$}", width = digits as usize);
+        Ok(format!("{whole}.{}", fraction.trim_end_matches('0')))
+    }
+
+    /// Parses a decimal price in quote tokens per whole token. Digits beyond
+    /// the fixed-point precision are rejected rather than rounded.
+    pub fn from_ui_price(ui_price: &str, quote_decimals: u8) -> Result<Self> {
+        let (scale, digits) = Self::ui_scale(quote_decimals)?;
+        let (whole, fraction) = ui_price.split_once('.').unwrap_or((ui_price, ""));
+
+        require!(
+            !(whole.is_empty() && fraction.is_empty())
+                && fraction.len() <= digits as usize
+                && whole.bytes().chain(fraction.bytes()).all(|byte| byte.is_ascii_digit()),
+            LiquidityPoolError::InvalidExchangeRate
+        );
+
+        let parse = |digits: &str| -> Result<u128> {
+            if digits.is_empty() {
+                return Ok(0);
+            }
+            digits.parse::<u128>().map_err(|_| LiquidityPoolError::MathOverflow.into())
+        };
+
+        // Below `scale`, so it cannot overflow.
+        let fraction_value = parse(fraction)? * 10u128.pow(digits - fraction.len() as u32);
+        let value = parse(whole)?
+            .checked_mul(scale)
+            .and_then(|value| value.checked_add(fraction_value))
+            .ok_or(LiquidityPoolError::MathOverflow)?;
+
+        Ok(Price::new(value))
+    }
+
+    /// `value` units per quote token, and the number of decimal digits that is.
+    fn ui_scale(quote_decimals: u8) -> Result<(u128, u32)> {
+        let digits = quote_decimals as u32 + PRICE_SCALE.ilog10();
+        let scale = 10u128.checked_pow(digits).ok_or(LiquidityPoolError::MathOverflow)?;
+        Ok((scale, digits))
+    }
+}
+
+/// Converts a spot price (lamports per whole token, scaled by `PRICE_SCALE`) into
+/// the legacy `exchange_rate` format: normalized token units per lamport times 1e6.
+pub fn price_to_exchange_rate(price: u128) -> Result<u64> {
+    require!(price > 0, LiquidityPoolError::InvalidExchangeRate);
+    let rate = mul_div_u128(1_000_000, NORMALIZED_UNITS_PER_TOKEN * PRICE_SCALE, price, false)?;
+    Ok(rate.clamp(MIN_EXCHANGE_RATE as u128, u64::MAX as u128) as u64)
+}
+
 pub fn normalize_token_amount(amount: u64, token_decimals: u8) -> Result<u64> {
     const SOL_DECIMALS: u8 = 9;
 
@@ -110,6 +278,27 @@ pub fn normalize_token_amount(amount: u64, token_decimals: u8) -> Result<u64> {
     }
 }
 
+pub fn denormalize_token_amount(amount: u64, token_decimals: u8, round_up: bool) -> Result<u64> {
+    const SOL_DECIMALS: u8 = 9;
+
+    if token_decimals == SOL_DECIMALS {
+        return Ok(amount);
+    }
+
+    if token_decimals > SOL_DECIMALS {
+        let scale_up = 10_u64.pow((token_decimals - SOL_DECIMALS) as u32);
+        amount.checked_mul(scale_up).ok_or(LiquidityPoolError::MathOverflow.into())
+    } else {
+        let scale_down = 10_u64.pow((SOL_DECIMALS - token_decimals) as u32);
+        let scaled = amount / scale_down;
+        if round_up && scaled * scale_down < amount {
+            safe_add(scaled, 1)
+        } else {
+            Ok(scaled)
+        }
+    }
+}
+
 pub fn get_effective_pool_reserves(
     virtual_sol_reserve: u64,
     virtual_token_reserve: u64,
@@ -160,20 +349,67 @@ pub fn calculate_swap_input_amount(
         .checked_sub(output_amount)
         .ok_or(LiquidityPoolError::InsufficientLiquidity)?;
 
-    safe_mul_div(input_reserve, output_amount, new_output_reserve)
+    // Rounded up so exact-out swaps never under-pay the pool.
+    u128_to_u64(mul_div_u128(input_reserve as u128, output_amount as u128, new_output_reserve as u128, true)?)
 }
 
-pub fn get_real_reserves_from_accounts(
-    pool_lamports: u64,
-    rent_exempt_minimum: u64,
-    pool_token_amount: u64,
-    token_decimals: u8,
-) -> Result<(u64, u64)> {
-    let real_sol = pool_lamports
-        .checked_sub(rent_exempt_minimum)
-        .unwrap_or(0);
+/// Deviation of `actual_amount` from `expected_amount` in basis points.
+pub fn calculate_slippage(expected_amount: u64, actual_amount: u64) -> u64 {
+    if expected_amount == 0 {
+        return 0;
+    }
+
+    let deviation = expected_amount.abs_diff(actual_amount) as u128;
+    (deviation * 10_000 / expected_amount as u128).min(u64::MAX as u128) as u64
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    let real_tokens = normalize_token_amount(pool_token_amount, token_decimals)?;
+    const SOL_DECIMALS: u8 = 9;
+
+    #[test]
+    fn ui_price_formats_exactly() {
+        // 28 lamports per token.
+        let price = Price::new(28 * PRICE_SCALE);
+        assert_eq!(price.to_ui_price(SOL_DECIMALS).unwrap(), "0.000000028");
+        assert_eq!(price.to_ui_price(6).unwrap(), "0.000028");
+
+        assert_eq!(Price::new(1_500_000_000 * PRICE_SCALE).to_ui_price(SOL_DECIMALS).unwrap(), "1.5");
+        assert_eq!(Price::ZERO.to_ui_price(SOL_DECIMALS).unwrap(), "0");
+        assert_eq!(Price::new(1).to_ui_price(SOL_DECIMALS).unwrap(), "0.000000000000000000001");
+        assert_eq!(
+            Price::new(u128::MAX).to_ui_price(SOL_DECIMALS).unwrap(),
+            "340282366920938463.463374607431768211455"
+        );
+    }
+
+    #[test]
+    fn ui_price_parses_exactly() {
+        assert_eq!(Price::from_ui_price("0.000000028", SOL_DECIMALS).unwrap(), Price::new(28 * PRICE_SCALE));
+        assert_eq!(Price::from_ui_price("1.5", SOL_DECIMALS).unwrap(), Price::new(1_500_000_000 * PRICE_SCALE));
+        assert_eq!(Price::from_ui_price("2", 6).unwrap(), Price::new(2_000_000 * PRICE_SCALE));
+        assert_eq!(Price::from_ui_price(".5", SOL_DECIMALS).unwrap(), Price::new(500_000_000 * PRICE_SCALE));
+        assert_eq!(Price::from_ui_price("0.000000000000000000001", SOL_DECIMALS).unwrap(), Price::new(1));
+    }
+
+    #[test]
+    fn ui_price_rejects_malformed_and_out_of_range_input() {
+        for ui_price in ["", ".", "-1", "1e9", "1.2.3", " 1", "0.0000000000000000000001"] {
+            assert!(Price::from_ui_price(ui_price, SOL_DECIMALS).is_err(), "{ui_price:?}");
+        }
+        assert!(Price::from_ui_price("340282366920938464", SOL_DECIMALS).is_err());
+        assert!(Price::ZERO.to_ui_price(27).is_err());
+    }
 
-    Ok((real_sol, real_tokens))
-}
\ No newline at end of file
+    #[test]
+    fn ui_price_round_trips() {
+        for value in [0, 1, 28 * PRICE_SCALE, 123_456_789_012_345_678_901, u128::MAX] {
+            for quote_decimals in [0, 6, SOL_DECIMALS] {
+                let price = Price::new(value);
+                let ui_price = price.to_ui_price(quote_decimals).unwrap();
+                assert_eq!(Price::from_ui_price(&ui_price, quote_decimals).unwrap(), price, "{ui_price}");
+            }
+        }
+    }
+}
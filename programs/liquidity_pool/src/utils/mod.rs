@@ -1,8 +1,15 @@
 // programs/liquidity_pool/src/utils/mod.rs
 pub mod auth;
+pub mod curve;
+pub mod lp;
 pub mod math;
+pub mod oracle;
+pub mod quote;
+pub mod token;
 pub mod validation;
+pub mod vault;
 
 pub use auth::check_authority;
 pub use math::*;
-pub use validation::{validate_swap_params, validate_fee, validate_reserves};
\ No newline at end of file
+pub use curve::{Curve, PoolCurve};
+pub use validation::{validate_swap_params, validate_token_decimals, validate_curve, validate_completion_threshold, validate_fee, validate_fee_shares, validate_reserves};
\ No newline at end of file
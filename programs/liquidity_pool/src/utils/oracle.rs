@@ -0,0 +1,52 @@
+// programs/liquidity_pool/src/utils/oracle.rs
+use anchor_lang::prelude::*;
+use crate::error::LiquidityPoolError;
+use crate::state::{Observation, PoolOracle};
+use crate::utils::math::mul_div_u128;
+
+/// Price accumulator value at `target`, interpolated between the observations
+/// around it. `current` is the accumulator extrapolated to now, which covers
+/// the span after the latest observation. The price is constant between
+/// consecutive observations, so the interpolation is exact.
+pub fn price_cumulative_at(oracle: &PoolOracle, target: i64, current: Observation) -> Result<u128> {
+    let oldest = oracle.oldest().ok_or(LiquidityPoolError::InvalidOracleWindow)?;
+    require!(target >= oldest.timestamp, LiquidityPoolError::InvalidOracleWindow);
+
+    let mut before = *oldest;
+    let mut after = current;
+
+    for observation in oracle.observations.iter() {
+        if observation.timestamp <= target && observation.timestamp > before.timestamp {
+            before = *observation;
+        }
+        if observation.timestamp > target && observation.timestamp < after.timestamp {
+            after = *observation;
+        }
+    }
+
+    if target >= after.timestamp || after.timestamp == before.timestamp {
+        return Ok(after.price_cumulative);
+    }
+
+    let delta = mul_div_u128(
+        after.price_cumulative.wrapping_sub(before.price_cumulative),
+        (target - before.timestamp) as u128,
+        (after.timestamp - before.timestamp) as u128,
+        false,
+    )?;
+
+    Ok(before.price_cumulative.wrapping_add(delta))
+}
+
+/// Time-weighted average spot price over the last `window` seconds, scaled by
+/// `PRICE_SCALE`.
+pub fn calculate_twap(oracle: &PoolOracle, window: u32, current: Observation) -> Result<u128> {
+    require!(window > 0, LiquidityPoolError::InvalidOracleWindow);
+
+    let target = current.timestamp
+        .checked_sub(window as i64)
+        .ok_or(LiquidityPoolError::MathOverflow)?;
+    let start = price_cumulative_at(oracle, target, current)?;
+
+    Ok(current.price_cumulative.wrapping_sub(start) / window as u128)
+}
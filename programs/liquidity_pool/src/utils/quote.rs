@@ -0,0 +1,280 @@
+// programs/liquidity_pool/src/utils/quote.rs
+use anchor_lang::prelude::*;
+use crate::error::LiquidityPoolError;
+use crate::state::{AmmPool, CompletionThreshold, LiquidityPool, SwapDirection, SwapMode, SwapQuote, NORMALIZED_UNITS_PER_TOKEN, PRICE_SCALE};
+use crate::utils::curve::{ConstantProductCurve, Curve};
+use crate::utils::math::*;
+use crate::utils::token::TransferFee;
+
+/// A swap priced against a pool's curve. Fees are charged on the SOL leg:
+/// `sol_amount` is what the user pays or receives, `curve_sol_amount` is what
+/// moves through the reserves. Token amounts are in raw token units:
+/// `token_amount` moves through the token vault and `user_token_amount` is
+/// what the user sends or receives across the mint's transfer fee.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CurveSwap {
+    pub token_amount: u64,
+    pub user_token_amount: u64,
+    pub normalized_token_amount: u64,
+    pub sol_amount: u64,
+    pub curve_sol_amount: u64,
+    pub fee_amount: u64,
+    pub completes_curve: bool,
+    /// Spot price before the swap.
+    pub spot_price: u128,
+    pub price_impact_bps: u64,
+}
+
+impl CurveSwap {
+    pub fn amount_in(&self, direction: SwapDirection) -> u64 {
+        match direction {
+            SwapDirection::Buy => self.sol_amount,
+            SwapDirection::Sell => self.user_token_amount,
+        }
+    }
+
+    pub fn amount_out(&self, direction: SwapDirection) -> u64 {
+        match direction {
+            SwapDirection::Buy => self.user_token_amount,
+            SwapDirection::Sell => self.sol_amount,
+        }
+    }
+}
+
+/// Normalized tokens left on the curve before it completes.
+pub fn remaining_curve_tokens(pool: &LiquidityPool, curve: &impl Curve) -> Result<u64> {
+    Ok(match pool.completion_threshold {
+        CompletionThreshold::None => u64::MAX,
+        CompletionThreshold::TokensSold(threshold) => threshold.saturating_sub(pool.tokens_sold),
+        CompletionThreshold::SolRaised(threshold) => {
+            curve.quote_out(SwapDirection::Buy, threshold.saturating_sub(pool.real_sol))?
+        },
+    })
+}
+
+/// Prices a swap exactly as the swap instruction executes it, including fees,
+/// the completion cap on buys, slippage bounds and reserve checks.
+/// `transfer_fee` is the token mint's: a sell's input and a buy's output
+/// name what the user's token account sends or receives, and the curve sees
+/// what the token vault does.
+pub fn quote_swap(
+    pool: &LiquidityPool,
+    token_decimals: u8,
+    direction: SwapDirection,
+    mode: SwapMode,
+    transfer_fee: &impl TransferFee,
+) -> Result<CurveSwap> {
+    let curve = pool.pricing_curve(token_decimals)?;
+    let spot_price = curve.spot_price()?;
+    let fee_basis_points = pool.fee_basis_points;
+
+    // The buy that reaches the completion threshold is capped to it.
+    let remaining_tokens = remaining_curve_tokens(pool, &curve)?;
+    let net_of_fee = |amount: u64| safe_sub(amount, transfer_fee.fee(amount)?);
+
+    let (token_amount, user_token_amount, sol_amount, curve_sol_amount, fee_amount, completes_curve) = match (direction, mode) {
+        (SwapDirection::Buy, SwapMode::ExactIn { amount_in, min_amount_out }) => {
+            require!(amount_in > 0, LiquidityPoolError::InvalidSwapAmount);
+
+            let fee_amount = calculate_fee(amount_in, fee_basis_points)?;
+            let curve_sol_input = safe_sub(amount_in, fee_amount)?;
+
+            let normalized_token_output = curve.quote_out(direction, curve_sol_input)?;
+
+            if normalized_token_output >= remaining_tokens {
+                let token_output = denormalize_token_amount(remaining_tokens, token_decimals, false)?;
+                let capped_token_output = normalize_token_amount(token_output, token_decimals)?;
+                let curve_sol_input = curve.quote_in(direction, capped_token_output)?;
+                let fee_amount = calculate_fee_on_net(curve_sol_input, fee_basis_points)?;
+                let received = net_of_fee(token_output)?;
+
+                require!(received >= min_amount_out, LiquidityPoolError::SlippageExceeded);
+
+                (token_output, received, safe_add(curve_sol_input, fee_amount)?, curve_sol_input, fee_amount, true)
+            } else {
+                let token_output = denormalize_token_amount(normalized_token_output, token_decimals, false)?;
+                let received = net_of_fee(token_output)?;
+
+                require!(received >= min_amount_out, LiquidityPoolError::SlippageExceeded);
+
+                (token_output, received, amount_in, curve_sol_input, fee_amount, false)
+            }
+        },
+        (SwapDirection::Buy, SwapMode::ExactOut { amount_out, max_amount_in }) => {
+            require!(amount_out > 0, LiquidityPoolError::InvalidSwapAmount);
+
+            // The vault sends enough for `amount_out` to arrive.
+            let requested_token_output = transfer_fee.gross_amount(amount_out)?;
+            let completes_curve = normalize_token_amount(requested_token_output, token_decimals)? >= remaining_tokens;
+            let token_output = if completes_curve {
+                denormalize_token_amount(remaining_tokens, token_decimals, false)?
+            } else {
+                requested_token_output
+            };
+
+            let normalized_token_output = normalize_token_amount(token_output, token_decimals)?;
+            let curve_sol_input = curve.quote_in(direction, normalized_token_output)?;
+
+            let fee_amount = calculate_fee_on_net(curve_sol_input, fee_basis_points)?;
+            let sol_input = safe_add(curve_sol_input, fee_amount)?;
+
+            require!(sol_input <= max_amount_in, LiquidityPoolError::SlippageExceeded);
+
+            (token_output, net_of_fee(token_output)?, sol_input, curve_sol_input, fee_amount, completes_curve)
+        },
+        (SwapDirection::Sell, SwapMode::ExactIn { amount_in, min_amount_out }) => {
+            require!(amount_in > 0, LiquidityPoolError::InvalidSwapAmount);
+
+            // The curve buys back what reaches the vault.
+            let token_input = net_of_fee(amount_in)?;
+            let normalized_token_input = normalize_token_amount(token_input, token_decimals)?;
+            let curve_sol_output = curve.quote_out(direction, normalized_token_input)?;
+
+            let fee_amount = calculate_fee(curve_sol_output, fee_basis_points)?;
+            let sol_output = safe_sub(curve_sol_output, fee_amount)?;
+
+            require!(sol_output >= min_amount_out, LiquidityPoolError::SlippageExceeded);
+
+            (token_input, amount_in, sol_output, curve_sol_output, fee_amount, false)
+        },
+        (SwapDirection::Sell, SwapMode::ExactOut { amount_out, max_amount_in }) => {
+            require!(amount_out > 0, LiquidityPoolError::InvalidSwapAmount);
+
+            let fee_amount = calculate_fee_on_net(amount_out, fee_basis_points)?;
+            let curve_sol_output = safe_add(amount_out, fee_amount)?;
+
+            let normalized_token_input = curve.quote_in(direction, curve_sol_output)?;
+            let token_input = denormalize_token_amount(normalized_token_input, token_decimals, true)?;
+            let sent = transfer_fee.gross_amount(token_input)?;
+
+            require!(sent <= max_amount_in, LiquidityPoolError::SlippageExceeded);
+
+            (token_input, sent, amount_out, curve_sol_output, fee_amount, false)
+        },
+    };
+
+    require!(token_amount > 0 && sol_amount > 0, LiquidityPoolError::InvalidSwapAmount);
+
+    match direction {
+        SwapDirection::Buy => {
+            require!(token_amount <= pool.real_token, LiquidityPoolError::InsufficientLiquidity);
+        },
+        SwapDirection::Sell => {
+            require!(curve_sol_amount <= pool.real_sol, LiquidityPoolError::InsufficientLiquidity);
+        },
+    }
+
+    let normalized_token_amount = normalize_token_amount(token_amount, token_decimals)?;
+    let expected_sol_amount = u128_to_u64(mul_div_u128(
+        normalized_token_amount as u128,
+        spot_price,
+        NORMALIZED_UNITS_PER_TOKEN * PRICE_SCALE,
+        false,
+    )?)?;
+
+    Ok(CurveSwap {
+        token_amount,
+        user_token_amount,
+        normalized_token_amount,
+        sol_amount,
+        curve_sol_amount,
+        fee_amount,
+        completes_curve,
+        spot_price,
+        price_impact_bps: calculate_slippage(expected_sol_amount, curve_sol_amount),
+    })
+}
+
+/// Moves the pool's real reserves and curve position by a quoted swap.
+pub fn apply_swap(pool: &mut LiquidityPool, direction: SwapDirection, swap: &CurveSwap) -> Result<()> {
+    match direction {
+        SwapDirection::Buy => {
+            pool.real_sol = safe_add(pool.real_sol, swap.curve_sol_amount)?;
+            pool.real_token = safe_sub(pool.real_token, swap.token_amount)?;
+            pool.tokens_sold = safe_add(pool.tokens_sold, swap.normalized_token_amount)?;
+        },
+        SwapDirection::Sell => {
+            pool.real_sol = safe_sub(pool.real_sol, swap.curve_sol_amount)?;
+            pool.real_token = safe_add(pool.real_token, swap.token_amount)?;
+            pool.tokens_sold = pool.tokens_sold.saturating_sub(swap.normalized_token_amount);
+        },
+    }
+
+    Ok(())
+}
+
+/// Quote returned to clients: the swap priced by `quote_swap` plus the spot
+/// price the pool would move to.
+pub fn simulate_swap(
+    pool: &LiquidityPool,
+    token_decimals: u8,
+    direction: SwapDirection,
+    mode: SwapMode,
+    transfer_fee: &impl TransferFee,
+) -> Result<SwapQuote> {
+    let swap = quote_swap(pool, token_decimals, direction, mode, transfer_fee)?;
+
+    let mut pool_after = pool.clone();
+    apply_swap(&mut pool_after, direction, &swap)?;
+
+    Ok(SwapQuote {
+        amount_in: swap.amount_in(direction),
+        amount_out: swap.amount_out(direction),
+        fee_amount: swap.fee_amount,
+        price_impact_bps: swap.price_impact_bps,
+        new_spot_price: Price::new(pool_after.pricing_curve(token_decimals)?.spot_price()?),
+    })
+}
+
+/// A swap priced against a graduated AMM pool. The fee is taken from the
+/// input and left in the reserves for LPs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AmmQuote {
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub fee_amount: u64,
+    /// Spot price before the swap.
+    pub spot_price: u128,
+}
+
+pub fn quote_amm_swap(amm_pool: &AmmPool, direction: SwapDirection, mode: SwapMode) -> Result<AmmQuote> {
+    let fee_basis_points = amm_pool.fee_basis_points;
+    let curve = ConstantProductCurve {
+        sol_reserve: amm_pool.sol_reserve,
+        token_reserve: amm_pool.token_reserve,
+    };
+    let spot_price = curve.spot_price()?;
+
+    let (amount_in, amount_out, fee_amount) = match mode {
+        SwapMode::ExactIn { amount_in, min_amount_out } => {
+            require!(amount_in > 0, LiquidityPoolError::InvalidSwapAmount);
+
+            let fee_amount = calculate_fee(amount_in, fee_basis_points)?;
+            let amount_out = curve.quote_out(direction, safe_sub(amount_in, fee_amount)?)?;
+
+            require!(amount_out >= min_amount_out, LiquidityPoolError::SlippageExceeded);
+
+            (amount_in, amount_out, fee_amount)
+        },
+        SwapMode::ExactOut { amount_out, max_amount_in } => {
+            require!(amount_out > 0, LiquidityPoolError::InvalidSwapAmount);
+
+            let net_amount_in = curve.quote_in(direction, amount_out)?;
+            let fee_amount = calculate_fee_on_net(net_amount_in, fee_basis_points)?;
+            let amount_in = safe_add(net_amount_in, fee_amount)?;
+
+            require!(amount_in <= max_amount_in, LiquidityPoolError::SlippageExceeded);
+
+            (amount_in, amount_out, fee_amount)
+        },
+    };
+
+    require!(amount_out > 0, LiquidityPoolError::InvalidSwapAmount);
+
+    Ok(AmmQuote {
+        amount_in,
+        amount_out,
+        fee_amount,
+        spot_price,
+    })
+}
@@ -0,0 +1,216 @@
+// programs/liquidity_pool/src/utils/token.rs
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::instruction::harvest_withheld_tokens_to_mint;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::{TransferFeeAmount, TransferFeeConfig};
+use anchor_spl::token_2022::spl_token_2022::extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions};
+use anchor_spl::token_2022::spl_token_2022::{self, onchain};
+use anchor_spl::token_interface::{TokenAccount, TransferChecked};
+use crate::error::LiquidityPoolError;
+use crate::utils::math::safe_sub;
+
+/// Token-2022 mint extensions a pool can hold. Transfer fees are absorbed by
+/// measuring vault deltas and transfer hooks get their accounts forwarded;
+/// the rest only affect metadata or display. Anything else (permanent
+/// delegates, non-transferable or confidential mints, default-frozen accounts,
+/// closable mints) could strand or drain the vaults and is rejected.
+const SUPPORTED_MINT_EXTENSIONS: [ExtensionType; 5] = [
+    ExtensionType::TransferFeeConfig,
+    ExtensionType::TransferHook,
+    ExtensionType::MetadataPointer,
+    ExtensionType::TokenMetadata,
+    ExtensionType::InterestBearingConfig,
+];
+
+/// Quote mints are moved without measuring vault deltas, so only extensions
+/// that leave transfers untouched are allowed.
+const SUPPORTED_QUOTE_MINT_EXTENSIONS: [ExtensionType; 2] = [
+    ExtensionType::MetadataPointer,
+    ExtensionType::TokenMetadata,
+];
+
+pub fn validate_mint_extensions(mint: &AccountInfo) -> Result<()> {
+    check_mint_extensions(mint, &SUPPORTED_MINT_EXTENSIONS)
+}
+
+pub fn validate_quote_mint_extensions(mint: &AccountInfo) -> Result<()> {
+    check_mint_extensions(mint, &SUPPORTED_QUOTE_MINT_EXTENSIONS)
+}
+
+fn check_mint_extensions(mint: &AccountInfo, supported: &[ExtensionType]) -> Result<()> {
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(());
+    }
+
+    let mint_data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+
+    for extension in mint_state.get_extension_types()? {
+        require!(
+            supported.contains(&extension),
+            LiquidityPoolError::UnsupportedMintExtension
+        );
+    }
+
+    Ok(())
+}
+
+fn transfer_fee_config(mint_data: &[u8]) -> Result<Option<TransferFeeConfig>> {
+    let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(mint_data)?;
+    Ok(mint_state.get_extension::<TransferFeeConfig>().ok().copied())
+}
+
+/// Fee the mint withholds from a transfer of `amount` in `epoch`. Zero for
+/// legacy mints and mints without a transfer fee.
+pub fn calculate_transfer_fee(mint_data: &[u8], epoch: u64, amount: u64) -> Result<u64> {
+    match transfer_fee_config(mint_data)? {
+        Some(config) => config
+            .calculate_epoch_fee(epoch, amount)
+            .ok_or_else(|| LiquidityPoolError::MathOverflow.into()),
+        None => Ok(0),
+    }
+}
+
+/// Amount to send so that at least `net_amount` arrives after the transfer fee.
+pub fn calculate_gross_transfer_amount(mint_data: &[u8], epoch: u64, net_amount: u64) -> Result<u64> {
+    let fee = match transfer_fee_config(mint_data)? {
+        Some(config) if net_amount > 0 => config
+            .calculate_inverse_epoch_fee(epoch, net_amount)
+            .ok_or(LiquidityPoolError::MathOverflow)?,
+        _ => 0,
+    };
+
+    net_amount.checked_add(fee).ok_or_else(|| LiquidityPoolError::MathOverflow.into())
+}
+
+pub fn current_transfer_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    let epoch = Clock::get()?.epoch;
+    calculate_transfer_fee(&mint.try_borrow_data()?, epoch, amount)
+}
+
+pub fn current_gross_transfer_amount(mint: &AccountInfo, net_amount: u64) -> Result<u64> {
+    let epoch = Clock::get()?.epoch;
+    calculate_gross_transfer_amount(&mint.try_borrow_data()?, epoch, net_amount)
+}
+
+/// A token mint's transfer fee, as swap quotes apply it.
+pub trait TransferFee {
+    /// Fee withheld from a transfer of `amount`.
+    fn fee(&self, amount: u64) -> Result<u64>;
+
+    /// Amount to send so that at least `net_amount` arrives.
+    fn gross_amount(&self, net_amount: u64) -> Result<u64>;
+}
+
+/// A mint without the Token-2022 transfer fee extension.
+pub struct NoTransferFee;
+
+impl TransferFee for NoTransferFee {
+    fn fee(&self, _amount: u64) -> Result<u64> {
+        Ok(0)
+    }
+
+    fn gross_amount(&self, net_amount: u64) -> Result<u64> {
+        Ok(net_amount)
+    }
+}
+
+/// The fee of a mint's account data in a given epoch, for off-chain quotes.
+pub struct MintTransferFee<'a> {
+    pub mint_data: &'a [u8],
+    pub epoch: u64,
+}
+
+impl TransferFee for MintTransferFee<'_> {
+    fn fee(&self, amount: u64) -> Result<u64> {
+        calculate_transfer_fee(self.mint_data, self.epoch, amount)
+    }
+
+    fn gross_amount(&self, net_amount: u64) -> Result<u64> {
+        calculate_gross_transfer_amount(self.mint_data, self.epoch, net_amount)
+    }
+}
+
+/// The fee of a mint account in the current epoch.
+pub struct CurrentTransferFee<'a, 'info>(pub &'a AccountInfo<'info>);
+
+impl TransferFee for CurrentTransferFee<'_, '_> {
+    fn fee(&self, amount: u64) -> Result<u64> {
+        current_transfer_fee(self.0, amount)
+    }
+
+    fn gross_amount(&self, net_amount: u64) -> Result<u64> {
+        current_gross_transfer_amount(self.0, net_amount)
+    }
+}
+
+/// `transfer_checked` for either token program. Extra accounts a transfer
+/// hook needs are resolved from the context's remaining accounts.
+pub fn transfer_tokens<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, TransferChecked<'info>>,
+    amount: u64,
+    decimals: u8,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    onchain::invoke_transfer_checked(
+        ctx.program.key,
+        ctx.accounts.from,
+        ctx.accounts.mint,
+        ctx.accounts.to,
+        ctx.accounts.authority,
+        &ctx.remaining_accounts,
+        amount,
+        decimals,
+        ctx.signer_seeds,
+    )
+    .map_err(Into::into)
+}
+
+/// Transfers into one of the pool's vaults and returns what actually arrived,
+/// which is less than `amount` for mints that charge a transfer fee.
+pub fn transfer_tokens_to_vault<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, TransferChecked<'info>>,
+    vault: &mut InterfaceAccount<'info, TokenAccount>,
+    amount: u64,
+    decimals: u8,
+) -> Result<u64> {
+    let balance_before = vault.amount;
+
+    transfer_tokens(ctx, amount, decimals)?;
+    vault.reload()?;
+
+    safe_sub(vault.amount, balance_before)
+}
+
+/// Moves the Token-2022 transfer fees withheld in `account` to its mint, which
+/// a token account needs before it can be closed. Harvesting is
+/// permissionless, so nothing signs.
+pub fn harvest_withheld_tokens<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+) -> Result<()> {
+    if *account.owner != spl_token_2022::ID {
+        return Ok(());
+    }
+
+    let withheld_amount = {
+        let account_data = account.try_borrow_data()?;
+        let account_state = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&account_data)?;
+        account_state
+            .get_extension::<TransferFeeAmount>()
+            .map_or(0, |transfer_fee_amount| u64::from(transfer_fee_amount.withheld_amount))
+    };
+    if withheld_amount == 0 {
+        return Ok(());
+    }
+
+    invoke(
+        &harvest_withheld_tokens_to_mint(token_program.key, mint.key, &[account.key])?,
+        &[mint.clone(), account.clone(), token_program.clone()],
+    )
+    .map_err(Into::into)
+}
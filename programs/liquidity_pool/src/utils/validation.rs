@@ -2,6 +2,7 @@
 use anchor_lang::prelude::*;
 use crate::error::LiquidityPoolError;
 use crate::state::constants::*;
+use crate::state::{CompletionThreshold, CurveKind};
 
 pub fn validate_swap_params(amount: u64, reserve: u64) -> Result<()> {
     require!(amount >= MIN_SWAP_AMOUNT, LiquidityPoolError::InvalidSwapAmount);
@@ -9,6 +10,34 @@ pub fn validate_swap_params(amount: u64, reserve: u64) -> Result<()> {
     Ok(())
 }
 
+pub fn validate_token_decimals(token_decimals: u8) -> Result<()> {
+    require!(token_decimals <= MAX_TOKEN_DECIMALS, LiquidityPoolError::InvalidTokenDecimals);
+    Ok(())
+}
+
+pub fn validate_curve(curve: &CurveKind) -> Result<()> {
+    match *curve {
+        CurveKind::ConstantProduct => {},
+        CurveKind::Linear { base_price, .. } => {
+            require!(base_price > 0, LiquidityPoolError::InvalidCurveParams);
+        },
+        CurveKind::Exponential { base_price, growth_rate } => {
+            require!(base_price > 0 && growth_rate > 0, LiquidityPoolError::InvalidCurveParams);
+        },
+    }
+    Ok(())
+}
+
+pub fn validate_completion_threshold(threshold: &CompletionThreshold) -> Result<()> {
+    match *threshold {
+        CompletionThreshold::None => {},
+        CompletionThreshold::SolRaised(amount) | CompletionThreshold::TokensSold(amount) => {
+            require!(amount > 0, LiquidityPoolError::InvalidCurveParams);
+        },
+    }
+    Ok(())
+}
+
 pub fn validate_fee(fee_basis_points: u16) -> Result<()> {
     require!(
         fee_basis_points <= MAX_FEE_BASIS_POINTS,
@@ -17,6 +46,16 @@ pub fn validate_fee(fee_basis_points: u16) -> Result<()> {
     Ok(())
 }
 
+pub fn validate_fee_shares(
+    protocol_share_bps: u16,
+    creator_share_bps: u16,
+    referrer_share_bps: u16,
+) -> Result<()> {
+    let total = (protocol_share_bps as u32) + (creator_share_bps as u32) + (referrer_share_bps as u32);
+    require!(total == FEE_SHARE_TOTAL_BPS as u32, LiquidityPoolError::InvalidFeeShares);
+    Ok(())
+}
+
 pub fn validate_reserves(sol_reserve: u64, token_reserve: u64) -> Result<()> {
     require!(sol_reserve > 0 || token_reserve > 0, LiquidityPoolError::InvalidReserves);
     Ok(())
@@ -0,0 +1,219 @@
+// programs/liquidity_pool/src/utils/vault.rs
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{close_account, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::error::LiquidityPoolError;
+use crate::state::{AmmPool, LiquidityPool, AMM_SOL_VAULT_SEED, SOL_VAULT_SEED};
+use crate::utils::token::transfer_tokens;
+
+/// Rent-exempt minimum of the data-less system account backing a pool's SOL
+/// vault. Everything above it is reserve.
+pub fn sol_vault_rent_exempt_minimum() -> Result<u64> {
+    Ok(Rent::get()?.minimum_balance(0))
+}
+
+pub fn transfer_from_sol_vault<'info>(
+    system_program: &AccountInfo<'info>,
+    sol_vault: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    pool: &Pubkey,
+    sol_vault_bump: u8,
+    amount: u64,
+) -> Result<()> {
+    transfer_from_vault(system_program, sol_vault, to, &[SOL_VAULT_SEED, pool.as_ref(), &[sol_vault_bump]], amount)
+}
+
+/// `transfer_from_sol_vault` for the `amm_sol_vault` of a graduated AMM pool.
+pub fn transfer_from_amm_sol_vault<'info>(
+    system_program: &AccountInfo<'info>,
+    amm_sol_vault: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    amm_pool: &Pubkey,
+    sol_vault_bump: u8,
+    amount: u64,
+) -> Result<()> {
+    transfer_from_vault(
+        system_program,
+        amm_sol_vault,
+        to,
+        &[AMM_SOL_VAULT_SEED, amm_pool.as_ref(), &[sol_vault_bump]],
+        amount,
+    )
+}
+
+fn transfer_from_vault<'info>(
+    system_program: &AccountInfo<'info>,
+    vault: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    vault_seeds: &[&[u8]],
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    anchor_lang::system_program::transfer(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            anchor_lang::system_program::Transfer {
+                from: vault.clone(),
+                to: to.clone(),
+            },
+            &[vault_seeds]
+        ),
+        amount,
+    )
+}
+
+/// Closes an empty token account owned by `pool` and pays its rent to
+/// `destination`.
+pub fn close_pool_token_vault<'info>(
+    token_program: &AccountInfo<'info>,
+    vault: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+    pool: &Account<'info, LiquidityPool>,
+) -> Result<()> {
+    close_account(CpiContext::new_with_signer(
+        token_program.clone(),
+        CloseAccount {
+            account: vault.clone(),
+            destination: destination.clone(),
+            authority: pool.to_account_info(),
+        },
+        &[&pool.signer_seeds()]
+    ))
+}
+
+/// Quote leg of a pool quoted in an SPL token: its quote vault and the user's
+/// account of the same mint. SOL pools have none and move lamports instead.
+pub struct QuoteTokenAccounts<'info> {
+    pub mint: AccountInfo<'info>,
+    pub decimals: u8,
+    pub vault: AccountInfo<'info>,
+    pub user_account: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+}
+
+impl<'info> QuoteTokenAccounts<'info> {
+    /// `None` for SOL pools. Pools quoted in an SPL token must pass every
+    /// quote account.
+    pub fn load(
+        native_quote: bool,
+        mint: Option<&InterfaceAccount<'info, Mint>>,
+        vault: Option<&InterfaceAccount<'info, TokenAccount>>,
+        user_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+        token_program: Option<&Interface<'info, TokenInterface>>,
+    ) -> Result<Option<Self>> {
+        if native_quote {
+            return Ok(None);
+        }
+
+        match (mint, vault, user_account, token_program) {
+            (Some(mint), Some(vault), Some(user_account), Some(token_program)) => Ok(Some(Self {
+                mint: mint.to_account_info(),
+                decimals: mint.decimals,
+                vault: vault.to_account_info(),
+                user_account: user_account.to_account_info(),
+                token_program: token_program.to_account_info(),
+            })),
+            _ => err!(LiquidityPoolError::MissingQuoteAccounts),
+        }
+    }
+
+    /// Pays `amount` from the quote vault to the user's account, signed by the
+    /// pool that owns the vault.
+    pub fn transfer_out(&self, authority: AccountInfo<'info>, signer_seeds: &[&[u8]], amount: u64) -> Result<()> {
+        transfer_tokens(
+            CpiContext::new_with_signer(
+                self.token_program.clone(),
+                TransferChecked {
+                    from: self.vault.clone(),
+                    to: self.user_account.clone(),
+                    mint: self.mint.clone(),
+                    authority,
+                },
+                &[signer_seeds]
+            ),
+            amount,
+            self.decimals,
+        )
+    }
+}
+
+/// Moves `amount` of the quote asset from `user` into a pool: lamports into
+/// `native_vault` for SOL pools, tokens into the quote vault otherwise.
+pub fn transfer_quote_in<'info>(
+    quote: Option<&QuoteTokenAccounts<'info>>,
+    user: &AccountInfo<'info>,
+    native_vault: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    match quote {
+        Some(quote) => transfer_tokens(
+            CpiContext::new(
+                quote.token_program.clone(),
+                TransferChecked {
+                    from: quote.user_account.clone(),
+                    to: quote.vault.clone(),
+                    mint: quote.mint.clone(),
+                    authority: user.clone(),
+                },
+            ),
+            amount,
+            quote.decimals,
+        ),
+        None => anchor_lang::system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                anchor_lang::system_program::Transfer {
+                    from: user.clone(),
+                    to: native_vault.clone(),
+                },
+            ),
+            amount,
+        ),
+    }
+}
+
+/// Pays `amount` of a launch pool's quote reserve out: lamports from the
+/// `sol_vault` to `to` for SOL pools, tokens to the user's quote account
+/// otherwise.
+pub fn transfer_quote_out<'info>(
+    quote: Option<&QuoteTokenAccounts<'info>>,
+    pool: &Account<'info, LiquidityPool>,
+    sol_vault: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    match quote {
+        Some(quote) => quote.transfer_out(pool.to_account_info(), &pool.signer_seeds(), amount),
+        None => transfer_from_sol_vault(system_program, sol_vault, to, &pool.key(), pool.sol_vault_bump, amount),
+    }
+}
+
+/// `transfer_quote_out` for a graduated AMM pool and its `amm_sol_vault`.
+pub fn transfer_amm_quote_out<'info>(
+    quote: Option<&QuoteTokenAccounts<'info>>,
+    amm_pool: &Account<'info, AmmPool>,
+    amm_sol_vault: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    match quote {
+        Some(quote) => quote.transfer_out(amm_pool.to_account_info(), &amm_pool.signer_seeds(), amount),
+        None => transfer_from_amm_sol_vault(
+            system_program,
+            amm_sol_vault,
+            to,
+            &amm_pool.key(),
+            amm_pool.sol_vault_bump,
+            amount,
+        ),
+    }
+}
@@ -0,0 +1,391 @@
+// programs/liquidity_pool/tests/close_pool.rs
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{entrypoint::ProgramResult, sysvar};
+use anchor_lang::{system_program, InstructionData};
+use anchor_spl::token::spl_token;
+use liquidity_pool::state::{
+    CompletionThreshold, CurveKind, GlobalConfig, MintRegistry, PoolCreationPolicy, MINIMUM_LIQUIDITY, NATIVE_QUOTE_MINT,
+};
+use liquidity_pool::{accounts, instruction as ix};
+use solana_program_test::{processor, BanksClient, ProgramTest, ProgramTestContext};
+use solana_sdk::account::Account;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::program_pack::Pack;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::system_instruction;
+use solana_sdk::transaction::Transaction;
+
+const TOKEN_DECIMALS: u8 = 6;
+
+fn process_instruction(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    // `entry` wants the accounts to outlive the slice that holds them.
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    liquidity_pool::entry(program_id, accounts, data)
+}
+
+fn pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &liquidity_pool::ID).0
+}
+
+struct Pool {
+    token_mint: Pubkey,
+    quote_mint: Pubkey,
+    mint_registry: Pubkey,
+    pool_registry: Pubkey,
+    pool: Pubkey,
+    fee_vault: Pubkey,
+    sol_vault: Pubkey,
+    pool_token_account: Pubkey,
+    oracle: Pubkey,
+    protocol_fee_account: Pubkey,
+    lp_mint: Pubkey,
+    lp_lock_account: Pubkey,
+}
+
+impl Pool {
+    fn new(token_mint: Pubkey, quote_mint: Pubkey, pool_index: u8) -> Self {
+        let pool_registry = pda(&[b"pool_registry", token_mint.as_ref(), quote_mint.as_ref()]);
+        let pool = pda(&[b"pool", token_mint.as_ref(), quote_mint.as_ref(), &[pool_index]]);
+
+        Self {
+            token_mint,
+            quote_mint,
+            mint_registry: pda(&[b"mint_registry", token_mint.as_ref()]),
+            pool_registry,
+            pool,
+            fee_vault: pda(&[b"fee_vault", pool.as_ref()]),
+            sol_vault: pda(&[b"sol_vault", pool.as_ref()]),
+            pool_token_account: pda(&[b"pool_token", pool.as_ref()]),
+            oracle: pda(&[b"oracle", pool.as_ref()]),
+            protocol_fee_account: pda(&[b"fee_account", pool.as_ref(), b"protocol"]),
+            lp_mint: pda(&[b"lp_mint", pool.as_ref()]),
+            lp_lock_account: pda(&[b"lp_lock", pool.as_ref()]),
+        }
+    }
+
+    fn fee_account(&self, recipient: &Pubkey) -> Pubkey {
+        pda(&[b"fee_account", self.pool.as_ref(), recipient.as_ref()])
+    }
+}
+
+fn program_test() -> ProgramTest {
+    let mut program_test = ProgramTest::new("liquidity_pool", liquidity_pool::ID, processor!(process_instruction));
+    let rent = Rent::default();
+
+    // `initialize_global_config` needs the program's upgrade authority, which
+    // a natively loaded program does not have.
+    let global_config = GlobalConfig {
+        admin: Pubkey::new_unique(),
+        treasury: Pubkey::new_unique(),
+        protocol_share_bps: 2_000,
+        creator_share_bps: 8_000,
+        referrer_share_bps: 0,
+        default_fee_basis_points: 100,
+        pool_creation_policy: PoolCreationPolicy::Permissionless,
+        is_paused: false,
+        bump: Pubkey::find_program_address(&[b"global_config"], &liquidity_pool::ID).1,
+    };
+    let mut data = Vec::with_capacity(GlobalConfig::SPACE);
+    global_config.try_serialize(&mut data).unwrap();
+    data.resize(GlobalConfig::SPACE, 0);
+    program_test.add_account(
+        pda(&[b"global_config"]),
+        Account {
+            lamports: rent.minimum_balance(data.len()),
+            data,
+            owner: liquidity_pool::ID,
+            ..Account::default()
+        },
+    );
+
+    let mut native_mint = vec![0; spl_token::state::Mint::LEN];
+    spl_token::state::Mint {
+        decimals: 9,
+        is_initialized: true,
+        ..spl_token::state::Mint::default()
+    }
+    .pack_into_slice(&mut native_mint);
+    program_test.add_account(
+        NATIVE_QUOTE_MINT,
+        Account {
+            lamports: rent.minimum_balance(native_mint.len()),
+            data: native_mint,
+            owner: spl_token::ID,
+            ..Account::default()
+        },
+    );
+
+    program_test
+}
+
+async fn process(context: &mut ProgramTestContext, instructions: &[Instruction], signers: &[&Keypair]) {
+    let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+    let transaction = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&context.payer.pubkey()),
+        &all_signers,
+        blockhash,
+    );
+
+    context.banks_client.process_transaction(transaction).await.unwrap();
+}
+
+async fn create_mint(context: &mut ProgramTestContext) -> Pubkey {
+    let mint = Keypair::new();
+    let authority = context.payer.pubkey();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let instructions = [
+        system_instruction::create_account(
+            &authority,
+            &mint.pubkey(),
+            rent.minimum_balance(spl_token::state::Mint::LEN),
+            spl_token::state::Mint::LEN as u64,
+            &spl_token::ID,
+        ),
+        spl_token::instruction::initialize_mint2(&spl_token::ID, &mint.pubkey(), &authority, None, TOKEN_DECIMALS)
+            .unwrap(),
+    ];
+
+    process(context, &instructions, &[&mint]).await;
+    mint.pubkey()
+}
+
+async fn create_token_account(context: &mut ProgramTestContext, mint: &Pubkey, owner: &Pubkey) -> Pubkey {
+    let account = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let instructions = [
+        system_instruction::create_account(
+            &context.payer.pubkey(),
+            &account.pubkey(),
+            rent.minimum_balance(spl_token::state::Account::LEN),
+            spl_token::state::Account::LEN as u64,
+            &spl_token::ID,
+        ),
+        spl_token::instruction::initialize_account3(&spl_token::ID, &account.pubkey(), mint, owner).unwrap(),
+    ];
+
+    process(context, &instructions, &[&account]).await;
+    account.pubkey()
+}
+
+async fn token_balance(banks_client: &mut BanksClient, account: Pubkey) -> u64 {
+    let account = banks_client.get_account(account).await.unwrap().unwrap();
+    spl_token::state::Account::unpack(&account.data).unwrap().amount
+}
+
+async fn registered_quote_mints(banks_client: &mut BanksClient, mint_registry: Pubkey) -> Vec<Pubkey> {
+    let account = banks_client.get_account(mint_registry).await.unwrap().unwrap();
+    MintRegistry::try_deserialize(&mut &account.data[..]).unwrap().quote_mints
+}
+
+async fn lamports(banks_client: &mut BanksClient, account: Pubkey) -> u64 {
+    banks_client.get_account(account).await.unwrap().map_or(0, |account| account.lamports)
+}
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: liquidity_pool::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+fn create_pool(pool: &Pool, authority: Pubkey) -> [Instruction; 2] {
+    let create_pool = build(
+        accounts::CreatePool {
+            pool_registry: pool.pool_registry,
+            mint_registry: pool.mint_registry,
+            pool: pool.pool,
+            fee_vault: pool.fee_vault,
+            sol_vault: pool.sol_vault,
+            global_config: pda(&[b"global_config"]),
+            creator_allowlist_entry: None,
+            creator_fee_account: pool.fee_account(&authority),
+            protocol_fee_account: pool.protocol_fee_account,
+            oracle: pool.oracle,
+            pool_creator: authority,
+            token_mint: pool.token_mint,
+            quote_mint: pool.quote_mint,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        ix::CreatePool {
+            pool_index: 0,
+            virtual_token_reserve: None,
+            virtual_sol_reserve: Some(30_000_000_000),
+            curve: CurveKind::ConstantProduct,
+            completion_threshold: CompletionThreshold::None,
+        },
+    );
+    let create_pool_token_account = build(
+        accounts::CreatePoolTokenAccount {
+            pool: pool.pool,
+            payer: authority,
+            token_mint: pool.token_mint,
+            pool_token_account: pool.pool_token_account,
+            lp_mint: pool.lp_mint,
+            lp_lock_account: pool.lp_lock_account,
+            quote_mint: None,
+            quote_vault: None,
+            token_program: spl_token::ID,
+            quote_token_program: None,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        ix::CreatePoolTokenAccount {},
+    );
+
+    [create_pool, create_pool_token_account]
+}
+
+#[tokio::test]
+async fn close_pool_pays_out_the_residue_and_frees_the_pool_index() {
+    let mut context = program_test().start_with_context().await;
+    let authority = context.payer.pubkey();
+
+    let mint = create_mint(&mut context).await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    let user_token_account = create_token_account(&mut context, &mint, &authority).await;
+    let mint_to = spl_token::instruction::mint_to(
+        &spl_token::ID,
+        &mint,
+        &user_token_account,
+        &authority,
+        &[],
+        1_000_000 * 10u64.pow(TOKEN_DECIMALS as u32),
+    )
+    .unwrap();
+    process(&mut context, &[mint_to], &[]).await;
+
+    let pool = Pool::new(mint, NATIVE_QUOTE_MINT, 0);
+    process(&mut context, &create_pool(&pool, authority), &[]).await;
+    assert_eq!(registered_quote_mints(&mut context.banks_client, pool.mint_registry).await, vec![NATIVE_QUOTE_MINT]);
+
+    let user_lp_account = create_token_account(&mut context, &pool.lp_mint, &authority).await;
+    let add_liquidity = build(
+        accounts::AddLiquidity {
+            pool: pool.pool,
+            user: authority,
+            token_mint: pool.token_mint,
+            user_token_account,
+            pool_token_account: pool.pool_token_account,
+            sol_vault: pool.sol_vault,
+            quote_mint: None,
+            quote_vault: None,
+            user_quote_account: None,
+            lp_mint: pool.lp_mint,
+            user_lp_account,
+            lp_lock_account: pool.lp_lock_account,
+            token_program: spl_token::ID,
+            quote_token_program: None,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        ix::AddLiquidityV2 {
+            max_sol_amount: 1_000_000_000,
+            max_token_amount: 1_000_000 * 10u64.pow(TOKEN_DECIMALS as u32),
+            min_lp_amount: 0,
+        },
+    );
+    process(&mut context, &[add_liquidity], &[]).await;
+
+    let lp_amount = token_balance(&mut context.banks_client, user_lp_account).await;
+    assert!(lp_amount > 0);
+    assert_eq!(token_balance(&mut context.banks_client, pool.lp_lock_account).await, MINIMUM_LIQUIDITY);
+
+    let remove_liquidity = build(
+        accounts::RemoveLiquidity {
+            pool: pool.pool,
+            user: authority,
+            token_mint: pool.token_mint,
+            user_token_account,
+            pool_token_account: pool.pool_token_account,
+            sol_vault: pool.sol_vault,
+            quote_mint: None,
+            quote_vault: None,
+            user_quote_account: None,
+            lp_mint: pool.lp_mint,
+            user_lp_account,
+            token_program: spl_token::ID,
+            quote_token_program: None,
+            system_program: system_program::ID,
+        },
+        ix::RemoveLiquidityV2 {
+            lp_amount,
+            min_sol_amount: 0,
+            min_token_amount: 0,
+        },
+    );
+    process(&mut context, &[remove_liquidity], &[]).await;
+
+    let token_residue = token_balance(&mut context.banks_client, pool.pool_token_account).await;
+    let sol_vault_lamports = lamports(&mut context.banks_client, pool.sol_vault).await;
+    assert!(token_residue > 0);
+    assert!(sol_vault_lamports > rent.minimum_balance(0));
+
+    let user_tokens_before = token_balance(&mut context.banks_client, user_token_account).await;
+    let close_pool = build(
+        accounts::ClosePool {
+            pool: pool.pool,
+            pool_registry: pool.pool_registry,
+            mint_registry: pool.mint_registry,
+            fee_vault: pool.fee_vault,
+            sol_vault: pool.sol_vault,
+            oracle: pool.oracle,
+            creator_fee_account: pool.fee_account(&authority),
+            protocol_fee_account: pool.protocol_fee_account,
+            token_mint: pool.token_mint,
+            pool_token_account: pool.pool_token_account,
+            authority_token_account: user_token_account,
+            lp_mint: pool.lp_mint,
+            lp_lock_account: pool.lp_lock_account,
+            quote_mint: None,
+            quote_vault: None,
+            authority_quote_account: None,
+            authority,
+            token_program: spl_token::ID,
+            quote_token_program: None,
+            system_program: system_program::ID,
+        },
+        ix::ClosePool {},
+    );
+    process(&mut context, &[close_pool], &[]).await;
+
+    for closed in [
+        pool.pool,
+        pool.fee_vault,
+        pool.sol_vault,
+        pool.pool_token_account,
+        pool.oracle,
+        pool.lp_lock_account,
+        pool.fee_account(&authority),
+        pool.protocol_fee_account,
+    ] {
+        assert!(context.banks_client.get_account(closed).await.unwrap().is_none());
+    }
+    assert_eq!(
+        token_balance(&mut context.banks_client, user_token_account).await,
+        user_tokens_before + token_residue
+    );
+
+    assert!(registered_quote_mints(&mut context.banks_client, pool.mint_registry).await.is_empty());
+
+    let lp_mint = context.banks_client.get_account(pool.lp_mint).await.unwrap().unwrap();
+    assert_eq!(spl_token::state::Mint::unpack(&lp_mint.data).unwrap().supply, 0);
+
+    process(&mut context, &create_pool(&pool, authority), &[]).await;
+    assert!(context.banks_client.get_account(pool.pool).await.unwrap().is_some());
+    assert_eq!(registered_quote_mints(&mut context.banks_client, pool.mint_registry).await, vec![NATIVE_QUOTE_MINT]);
+    assert_eq!(token_balance(&mut context.banks_client, pool.lp_lock_account).await, 0);
+
+    let quote_mint = create_mint(&mut context).await;
+    let [create_quoted_pool, _] = create_pool(&Pool::new(mint, quote_mint, 0), authority);
+    process(&mut context, &[create_quoted_pool], &[]).await;
+    assert_eq!(
+        registered_quote_mints(&mut context.banks_client, pool.mint_registry).await,
+        vec![NATIVE_QUOTE_MINT, quote_mint]
+    );
+}
@@ -0,0 +1,53 @@
+// sdk/src/decode.rs
+use anchor_lang::prelude::*;
+use liquidity_pool::state::{
+    AmmPool, CreatorAllowlistEntry, FeeAccount, FeeVault, GlobalConfig, LiquidityPool, MintRegistry,
+    PoolOracle, PoolRegistry,
+};
+
+/// Decodes raw account data, checking the Anchor discriminator.
+pub fn decode_account<T: AccountDeserialize>(data: &[u8]) -> Result<T> {
+    T::try_deserialize(&mut &data[..])
+}
+
+pub fn decode_pool(data: &[u8]) -> Result<LiquidityPool> {
+    decode_account(data)
+}
+
+pub fn decode_amm_pool(data: &[u8]) -> Result<AmmPool> {
+    decode_account(data)
+}
+
+pub fn decode_pool_registry(data: &[u8]) -> Result<PoolRegistry> {
+    decode_account(data)
+}
+
+pub fn decode_mint_registry(data: &[u8]) -> Result<MintRegistry> {
+    decode_account(data)
+}
+
+pub fn decode_oracle(data: &[u8]) -> Result<PoolOracle> {
+    decode_account(data)
+}
+
+pub fn decode_global_config(data: &[u8]) -> Result<GlobalConfig> {
+    decode_account(data)
+}
+
+pub fn decode_creator_allowlist_entry(data: &[u8]) -> Result<CreatorAllowlistEntry> {
+    decode_account(data)
+}
+
+pub fn decode_fee_account(data: &[u8]) -> Result<FeeAccount> {
+    decode_account(data)
+}
+
+pub fn decode_fee_vault(data: &[u8]) -> Result<FeeVault> {
+    decode_account(data)
+}
+
+/// Decodes the return data of `get_pool_info`, `calculate_swap` or `get_twap`
+/// from a simulated transaction.
+pub fn decode_return_data<T: AnchorDeserialize>(data: &[u8]) -> Result<T> {
+    T::try_from_slice(data).map_err(Into::into)
+}
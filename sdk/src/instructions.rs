@@ -0,0 +1,706 @@
+// sdk/src/instructions.rs
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::{system_program, sysvar};
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::token::spl_token::native_mint;
+use liquidity_pool::accounts;
+use liquidity_pool::instruction as ix;
+use liquidity_pool::state::SwapDirection;
+use liquidity_pool::ID;
+
+use crate::pda::*;
+
+fn build(accounts: impl ToAccountMetas, args: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts.to_account_metas(None),
+        data: args.data(),
+    }
+}
+
+/// Optional quote accounts of a pool quoted in an SPL token. All `None` for
+/// SOL pools, which move lamports instead.
+struct QuoteAccounts {
+    quote_mint: Option<Pubkey>,
+    quote_vault: Option<Pubkey>,
+    user_quote_account: Option<Pubkey>,
+    quote_token_program: Option<Pubkey>,
+}
+
+impl QuoteAccounts {
+    fn new(
+        native_quote: bool,
+        quote_mint: Pubkey,
+        quote_vault: Pubkey,
+        quote_token_program: Pubkey,
+        user_quote_account: Option<Pubkey>,
+    ) -> Self {
+        if native_quote {
+            return Self {
+                quote_mint: None,
+                quote_vault: None,
+                user_quote_account: None,
+                quote_token_program: None,
+            };
+        }
+
+        Self {
+            quote_mint: Some(quote_mint),
+            quote_vault: Some(quote_vault),
+            user_quote_account,
+            quote_token_program: Some(quote_token_program),
+        }
+    }
+
+    fn of_pool(addresses: &PoolAddresses, user_quote_account: Option<Pubkey>) -> Self {
+        Self::new(
+            addresses.is_native_quote(),
+            addresses.quote_mint,
+            addresses.quote_vault,
+            addresses.quote_token_program,
+            user_quote_account,
+        )
+    }
+
+    fn of_amm_pool(addresses: &AmmPoolAddresses, user_quote_account: Option<Pubkey>) -> Self {
+        Self::new(
+            addresses.is_native_quote(),
+            addresses.quote_mint,
+            addresses.amm_quote_vault,
+            addresses.quote_token_program,
+            user_quote_account,
+        )
+    }
+}
+
+/// Pass `allowlisted = true` when the pool creation policy requires the
+/// creator's allowlist entry.
+pub fn create_pool(
+    pool_creator: Pubkey,
+    addresses: &PoolAddresses,
+    allowlisted: bool,
+    args: ix::CreatePool,
+) -> Instruction {
+    build(
+        accounts::CreatePool {
+            pool_registry: addresses.pool_registry,
+            mint_registry: addresses.mint_registry,
+            pool: addresses.pool,
+            fee_vault: addresses.fee_vault,
+            sol_vault: addresses.sol_vault,
+            global_config: find_global_config().0,
+            creator_allowlist_entry: allowlisted.then(|| find_creator_allowlist_entry(&pool_creator).0),
+            creator_fee_account: find_fee_account(&addresses.pool, &pool_creator).0,
+            protocol_fee_account: find_protocol_fee_account(&addresses.pool).0,
+            oracle: addresses.oracle,
+            pool_creator,
+            token_mint: addresses.token_mint,
+            quote_mint: addresses.quote_mint,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        args,
+    )
+}
+
+pub fn create_pool_token_account(payer: Pubkey, addresses: &PoolAddresses) -> Instruction {
+    let quote = QuoteAccounts::of_pool(addresses, None);
+
+    build(
+        accounts::CreatePoolTokenAccount {
+            pool: addresses.pool,
+            payer,
+            token_mint: addresses.token_mint,
+            pool_token_account: addresses.pool_token_account,
+            lp_mint: addresses.lp_mint,
+            lp_lock_account: addresses.lp_lock_account,
+            quote_mint: quote.quote_mint,
+            quote_vault: quote.quote_vault,
+            token_program: addresses.token_program,
+            quote_token_program: quote.quote_token_program,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        ix::CreatePoolTokenAccount {},
+    )
+}
+
+fn add_liquidity_accounts(
+    user: Pubkey,
+    addresses: &PoolAddresses,
+    user_token_account: Pubkey,
+    user_quote_account: Option<Pubkey>,
+    user_lp_account: Pubkey,
+) -> accounts::AddLiquidity {
+    let quote = QuoteAccounts::of_pool(addresses, user_quote_account);
+
+    accounts::AddLiquidity {
+        pool: addresses.pool,
+        user,
+        token_mint: addresses.token_mint,
+        user_token_account,
+        pool_token_account: addresses.pool_token_account,
+        sol_vault: addresses.sol_vault,
+        quote_mint: quote.quote_mint,
+        quote_vault: quote.quote_vault,
+        user_quote_account: quote.user_quote_account,
+        lp_mint: addresses.lp_mint,
+        user_lp_account,
+        lp_lock_account: addresses.lp_lock_account,
+        token_program: addresses.token_program,
+        quote_token_program: quote.quote_token_program,
+        system_program: system_program::ID,
+        rent: sysvar::rent::ID,
+    }
+}
+
+pub fn add_liquidity(
+    user: Pubkey,
+    addresses: &PoolAddresses,
+    user_token_account: Pubkey,
+    user_quote_account: Option<Pubkey>,
+    user_lp_account: Pubkey,
+    args: ix::AddLiquidity,
+) -> Instruction {
+    build(add_liquidity_accounts(user, addresses, user_token_account, user_quote_account, user_lp_account), args)
+}
+
+pub fn add_liquidity_v2(
+    user: Pubkey,
+    addresses: &PoolAddresses,
+    user_token_account: Pubkey,
+    user_quote_account: Option<Pubkey>,
+    user_lp_account: Pubkey,
+    args: ix::AddLiquidityV2,
+) -> Instruction {
+    build(add_liquidity_accounts(user, addresses, user_token_account, user_quote_account, user_lp_account), args)
+}
+
+fn remove_liquidity_accounts(
+    user: Pubkey,
+    addresses: &PoolAddresses,
+    user_token_account: Pubkey,
+    user_quote_account: Option<Pubkey>,
+    user_lp_account: Pubkey,
+) -> accounts::RemoveLiquidity {
+    let quote = QuoteAccounts::of_pool(addresses, user_quote_account);
+
+    accounts::RemoveLiquidity {
+        pool: addresses.pool,
+        user,
+        token_mint: addresses.token_mint,
+        user_token_account,
+        pool_token_account: addresses.pool_token_account,
+        sol_vault: addresses.sol_vault,
+        quote_mint: quote.quote_mint,
+        quote_vault: quote.quote_vault,
+        user_quote_account: quote.user_quote_account,
+        lp_mint: addresses.lp_mint,
+        user_lp_account,
+        token_program: addresses.token_program,
+        quote_token_program: quote.quote_token_program,
+        system_program: system_program::ID,
+    }
+}
+
+pub fn remove_liquidity(
+    user: Pubkey,
+    addresses: &PoolAddresses,
+    user_token_account: Pubkey,
+    user_quote_account: Option<Pubkey>,
+    user_lp_account: Pubkey,
+    args: ix::RemoveLiquidity,
+) -> Instruction {
+    build(remove_liquidity_accounts(user, addresses, user_token_account, user_quote_account, user_lp_account), args)
+}
+
+pub fn remove_liquidity_v2(
+    user: Pubkey,
+    addresses: &PoolAddresses,
+    user_token_account: Pubkey,
+    user_quote_account: Option<Pubkey>,
+    user_lp_account: Pubkey,
+    args: ix::RemoveLiquidityV2,
+) -> Instruction {
+    build(remove_liquidity_accounts(user, addresses, user_token_account, user_quote_account, user_lp_account), args)
+}
+
+/// Fee recipients of a swap besides the protocol: the pool's `authority` and
+/// an optional referrer whose fee account already exists.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SwapFeeRecipients {
+    pub creator: Pubkey,
+    pub referrer: Option<Pubkey>,
+}
+
+fn swap_accounts(
+    user: Pubkey,
+    addresses: &PoolAddresses,
+    user_token_account: Pubkey,
+    user_quote_account: Option<Pubkey>,
+    fee_recipients: SwapFeeRecipients,
+) -> accounts::Swap {
+    let quote = QuoteAccounts::of_pool(addresses, user_quote_account);
+
+    accounts::Swap {
+        pool: addresses.pool,
+        fee_vault: addresses.fee_vault,
+        sol_vault: addresses.sol_vault,
+        oracle: addresses.oracle,
+        global_config: find_global_config().0,
+        protocol_fee_account: find_protocol_fee_account(&addresses.pool).0,
+        creator_fee_account: find_fee_account(&addresses.pool, &fee_recipients.creator).0,
+        referrer_fee_account: fee_recipients
+            .referrer
+            .map(|referrer| find_fee_account(&addresses.pool, &referrer).0),
+        user,
+        token_mint: addresses.token_mint,
+        user_token_account,
+        pool_token_account: addresses.pool_token_account,
+        quote_mint: quote.quote_mint,
+        quote_vault: quote.quote_vault,
+        user_quote_account: quote.user_quote_account,
+        token_program: addresses.token_program,
+        quote_token_program: quote.quote_token_program,
+        system_program: system_program::ID,
+        event_authority: find_event_authority().0,
+        program: ID,
+    }
+}
+
+pub fn swap(
+    user: Pubkey,
+    addresses: &PoolAddresses,
+    user_token_account: Pubkey,
+    user_quote_account: Option<Pubkey>,
+    fee_recipients: SwapFeeRecipients,
+    args: ix::Swap,
+) -> Instruction {
+    build(swap_accounts(user, addresses, user_token_account, user_quote_account, fee_recipients), args)
+}
+
+pub fn swap_v2(
+    user: Pubkey,
+    addresses: &PoolAddresses,
+    user_token_account: Pubkey,
+    user_quote_account: Option<Pubkey>,
+    fee_recipients: SwapFeeRecipients,
+    args: ix::SwapV2,
+) -> Instruction {
+    build(swap_accounts(user, addresses, user_token_account, user_quote_account, fee_recipients), args)
+}
+
+/// `user_wsol_account` pays the SOL leg of a buy and receives it on a sell.
+/// Buys unwrap through the user's ephemeral swap WSOL account, whose rent the
+/// user fronts and gets back within the instruction. SOL pools only.
+pub fn swap_wsol(
+    user: Pubkey,
+    addresses: &PoolAddresses,
+    user_token_account: Pubkey,
+    user_wsol_account: Pubkey,
+    fee_recipients: SwapFeeRecipients,
+    args: ix::SwapWsol,
+) -> Instruction {
+    build(
+        accounts::SwapWsol {
+            swap: swap_accounts(user, addresses, user_token_account, None, fee_recipients),
+            wsol_mint: native_mint::ID,
+            user_wsol_account,
+            swap_wsol_account: (args.direction == SwapDirection::Buy)
+                .then(|| find_swap_wsol_account(&addresses.pool, &user).0),
+            wsol_token_program: anchor_spl::token::ID,
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
+
+/// One pool of a `swap_via_sol` route, with the user's token account for its
+/// mint and, for pools quoted in an SPL token, for the shared quote mint.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SwapLeg<'a> {
+    pub addresses: &'a PoolAddresses,
+    pub user_token_account: Pubkey,
+    pub user_quote_account: Option<Pubkey>,
+    pub fee_recipients: SwapFeeRecipients,
+}
+
+pub fn swap_via_sol(user: Pubkey, sell: SwapLeg, buy: SwapLeg, args: ix::SwapViaSol) -> Instruction {
+    build(
+        accounts::SwapViaSol {
+            sell: swap_accounts(user, sell.addresses, sell.user_token_account, sell.user_quote_account, sell.fee_recipients),
+            buy: swap_accounts(user, buy.addresses, buy.user_token_account, buy.user_quote_account, buy.fee_recipients),
+        },
+        args,
+    )
+}
+
+pub fn migrate(caller: Pubkey, addresses: &PoolAddresses) -> Instruction {
+    let amm_addresses = AmmPoolAddresses::with_quote(
+        addresses.token_mint,
+        addresses.token_program,
+        addresses.quote_mint,
+        addresses.quote_token_program,
+        addresses.pool_index,
+    );
+    let quote = QuoteAccounts::of_pool(addresses, None);
+
+    build(
+        accounts::Migrate {
+            pool: addresses.pool,
+            token_mint: addresses.token_mint,
+            pool_token_account: addresses.pool_token_account,
+            sol_vault: addresses.sol_vault,
+            amm_pool: amm_addresses.amm_pool,
+            amm_token_vault: amm_addresses.amm_token_vault,
+            amm_sol_vault: amm_addresses.amm_sol_vault,
+            lp_mint: amm_addresses.lp_mint,
+            lp_lock_account: amm_addresses.lp_lock_account,
+            quote_mint: quote.quote_mint,
+            quote_vault: quote.quote_vault,
+            amm_quote_vault: quote.quote_vault.map(|_| amm_addresses.amm_quote_vault),
+            caller,
+            token_program: addresses.token_program,
+            quote_token_program: quote.quote_token_program,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        ix::Migrate {},
+    )
+}
+
+pub fn sync(caller: Pubkey, addresses: &PoolAddresses) -> Instruction {
+    build(
+        accounts::SyncPool {
+            pool: addresses.pool,
+            fee_vault: addresses.fee_vault,
+            token_mint: addresses.token_mint,
+            sol_vault: addresses.sol_vault,
+            pool_token_account: addresses.pool_token_account,
+            quote_vault: QuoteAccounts::of_pool(addresses, None).quote_vault,
+            caller,
+        },
+        ix::Sync {},
+    )
+}
+
+pub fn skim(
+    authority: Pubkey,
+    addresses: &PoolAddresses,
+    recipient: Pubkey,
+    recipient_token_account: Pubkey,
+    recipient_quote_account: Option<Pubkey>,
+) -> Instruction {
+    let quote = QuoteAccounts::of_pool(addresses, recipient_quote_account);
+
+    build(
+        accounts::SkimPool {
+            pool: addresses.pool,
+            fee_vault: addresses.fee_vault,
+            token_mint: addresses.token_mint,
+            sol_vault: addresses.sol_vault,
+            pool_token_account: addresses.pool_token_account,
+            authority,
+            recipient,
+            recipient_token_account,
+            quote_mint: quote.quote_mint,
+            quote_vault: quote.quote_vault,
+            recipient_quote_account: quote.user_quote_account,
+            token_program: addresses.token_program,
+            quote_token_program: quote.quote_token_program,
+            system_program: system_program::ID,
+        },
+        ix::Skim {},
+    )
+}
+
+fn amm_liquidity_accounts(
+    user: Pubkey,
+    addresses: &AmmPoolAddresses,
+    user_token_account: Pubkey,
+    user_quote_account: Option<Pubkey>,
+    user_lp_account: Pubkey,
+) -> accounts::AmmLiquidity {
+    let quote = QuoteAccounts::of_amm_pool(addresses, user_quote_account);
+
+    accounts::AmmLiquidity {
+        amm_pool: addresses.amm_pool,
+        token_mint: addresses.token_mint,
+        amm_token_vault: addresses.amm_token_vault,
+        sol_vault: addresses.amm_sol_vault,
+        lp_mint: addresses.lp_mint,
+        user,
+        user_token_account,
+        user_lp_account,
+        lp_lock_account: addresses.lp_lock_account,
+        quote_mint: quote.quote_mint,
+        quote_vault: quote.quote_vault,
+        user_quote_account: quote.user_quote_account,
+        token_program: addresses.token_program,
+        quote_token_program: quote.quote_token_program,
+        system_program: system_program::ID,
+    }
+}
+
+pub fn amm_add_liquidity(
+    user: Pubkey,
+    addresses: &AmmPoolAddresses,
+    user_token_account: Pubkey,
+    user_quote_account: Option<Pubkey>,
+    user_lp_account: Pubkey,
+    args: ix::AmmAddLiquidity,
+) -> Instruction {
+    build(amm_liquidity_accounts(user, addresses, user_token_account, user_quote_account, user_lp_account), args)
+}
+
+pub fn amm_remove_liquidity(
+    user: Pubkey,
+    addresses: &AmmPoolAddresses,
+    user_token_account: Pubkey,
+    user_quote_account: Option<Pubkey>,
+    user_lp_account: Pubkey,
+    args: ix::AmmRemoveLiquidity,
+) -> Instruction {
+    build(amm_liquidity_accounts(user, addresses, user_token_account, user_quote_account, user_lp_account), args)
+}
+
+pub fn amm_swap(
+    user: Pubkey,
+    addresses: &AmmPoolAddresses,
+    user_token_account: Pubkey,
+    user_quote_account: Option<Pubkey>,
+    args: ix::AmmSwap,
+) -> Instruction {
+    let quote = QuoteAccounts::of_amm_pool(addresses, user_quote_account);
+
+    build(
+        accounts::AmmSwap {
+            amm_pool: addresses.amm_pool,
+            token_mint: addresses.token_mint,
+            amm_token_vault: addresses.amm_token_vault,
+        sol_vault: addresses.amm_sol_vault,
+            global_config: find_global_config().0,
+            user,
+            user_token_account,
+            quote_mint: quote.quote_mint,
+            quote_vault: quote.quote_vault,
+            user_quote_account: quote.user_quote_account,
+            token_program: addresses.token_program,
+            quote_token_program: quote.quote_token_program,
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
+
+pub fn update_exchange_rate(authority: Pubkey, addresses: &PoolAddresses, args: ix::UpdateExchangeRate) -> Instruction {
+    build(
+        accounts::UpdateExchangeRate {
+            pool: addresses.pool,
+            authority,
+        },
+        args,
+    )
+}
+
+pub fn set_fee(authority: Pubkey, addresses: &PoolAddresses, args: ix::SetFee) -> Instruction {
+    build(
+        accounts::SetFee {
+            pool: addresses.pool,
+            authority,
+        },
+        args,
+    )
+}
+
+pub fn claim_fees(recipient: Pubkey, addresses: &PoolAddresses, recipient_quote_account: Option<Pubkey>) -> Instruction {
+    let quote = QuoteAccounts::of_pool(addresses, recipient_quote_account);
+
+    build(
+        accounts::ClaimFees {
+            pool: addresses.pool,
+            fee_vault: addresses.fee_vault,
+            fee_account: find_fee_account(&addresses.pool, &recipient).0,
+            recipient,
+            quote_mint: quote.quote_mint,
+            quote_vault: quote.quote_vault,
+            recipient_quote_account: quote.user_quote_account,
+            quote_token_program: quote.quote_token_program,
+        },
+        ix::ClaimFees {},
+    )
+}
+
+/// Pays the protocol's fees of a pool to `treasury`, which must be the
+/// current `GlobalConfig::treasury`. Anyone can send it.
+pub fn claim_protocol_fees(
+    addresses: &PoolAddresses,
+    treasury: Pubkey,
+    treasury_quote_account: Option<Pubkey>,
+) -> Instruction {
+    let quote = QuoteAccounts::of_pool(addresses, treasury_quote_account);
+
+    build(
+        accounts::ClaimProtocolFees {
+            pool: addresses.pool,
+            fee_vault: addresses.fee_vault,
+            global_config: find_global_config().0,
+            protocol_fee_account: find_protocol_fee_account(&addresses.pool).0,
+            treasury,
+            quote_mint: quote.quote_mint,
+            quote_vault: quote.quote_vault,
+            treasury_quote_account: quote.user_quote_account,
+            quote_token_program: quote.quote_token_program,
+        },
+        ix::ClaimProtocolFees {},
+    )
+}
+
+pub fn initialize_global_config(admin: Pubkey, args: ix::InitializeGlobalConfig) -> Instruction {
+    build(
+        accounts::InitializeGlobalConfig {
+            global_config: find_global_config().0,
+            admin,
+            program: ID,
+            program_data: find_program_data().0,
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
+
+pub fn update_global_config(admin: Pubkey, args: ix::UpdateGlobalConfig) -> Instruction {
+    build(
+        accounts::UpdateGlobalConfig {
+            global_config: find_global_config().0,
+            admin,
+        },
+        args,
+    )
+}
+
+pub fn set_paused(admin: Pubkey, args: ix::SetPaused) -> Instruction {
+    build(
+        accounts::UpdateGlobalConfig {
+            global_config: find_global_config().0,
+            admin,
+        },
+        args,
+    )
+}
+
+pub fn add_allowed_creator(admin: Pubkey, args: ix::AddAllowedCreator) -> Instruction {
+    build(
+        accounts::AddAllowedCreator {
+            global_config: find_global_config().0,
+            allowlist_entry: find_creator_allowlist_entry(&args.creator).0,
+            admin,
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
+
+pub fn remove_allowed_creator(admin: Pubkey, creator: Pubkey) -> Instruction {
+    build(
+        accounts::RemoveAllowedCreator {
+            global_config: find_global_config().0,
+            allowlist_entry: find_creator_allowlist_entry(&creator).0,
+            admin,
+        },
+        ix::RemoveAllowedCreator {},
+    )
+}
+
+pub fn init_fee_account(payer: Pubkey, addresses: &PoolAddresses, args: ix::InitFeeAccount) -> Instruction {
+    let pool = addresses.pool;
+
+    build(
+        accounts::InitFeeAccount {
+            pool,
+            fee_account: find_fee_account(&pool, &args.recipient).0,
+            payer,
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
+
+pub fn toggle_pool(authority: Pubkey, addresses: &PoolAddresses) -> Instruction {
+    build(
+        accounts::TogglePool {
+            pool: addresses.pool,
+            authority,
+        },
+        ix::TogglePool {},
+    )
+}
+
+/// Closes a pool whose liquidity providers have all withdrawn. Whatever is
+/// left in the vaults is paid to the authority's token and quote accounts.
+pub fn close_pool(
+    authority: Pubkey,
+    addresses: &PoolAddresses,
+    authority_token_account: Pubkey,
+    authority_quote_account: Option<Pubkey>,
+) -> Instruction {
+    let quote = QuoteAccounts::of_pool(addresses, authority_quote_account);
+
+    build(
+        accounts::ClosePool {
+            pool: addresses.pool,
+            pool_registry: addresses.pool_registry,
+            mint_registry: addresses.mint_registry,
+            fee_vault: addresses.fee_vault,
+            sol_vault: addresses.sol_vault,
+            oracle: addresses.oracle,
+            creator_fee_account: find_fee_account(&addresses.pool, &authority).0,
+            protocol_fee_account: find_protocol_fee_account(&addresses.pool).0,
+            token_mint: addresses.token_mint,
+            pool_token_account: addresses.pool_token_account,
+            authority_token_account,
+            lp_mint: addresses.lp_mint,
+            lp_lock_account: addresses.lp_lock_account,
+            quote_mint: quote.quote_mint,
+            quote_vault: quote.quote_vault,
+            authority_quote_account: quote.user_quote_account,
+            authority,
+            token_program: addresses.token_program,
+            quote_token_program: quote.quote_token_program,
+            system_program: system_program::ID,
+        },
+        ix::ClosePool {},
+    )
+}
+
+pub fn get_pool_info(addresses: &PoolAddresses) -> Instruction {
+    build(
+        accounts::GetPoolInfo {
+            pool: addresses.pool,
+            token_mint: addresses.token_mint,
+        },
+        ix::GetPoolInfo {},
+    )
+}
+
+pub fn calculate_swap(addresses: &PoolAddresses, args: ix::CalculateSwap) -> Instruction {
+    build(
+        accounts::CalculateSwap {
+            pool: addresses.pool,
+            token_mint: addresses.token_mint,
+        },
+        args,
+    )
+}
+
+pub fn get_twap(addresses: &PoolAddresses, args: ix::GetTwap) -> Instruction {
+    build(
+        accounts::GetTwap {
+            pool: addresses.pool,
+            token_mint: addresses.token_mint,
+            oracle: addresses.oracle,
+        },
+        args,
+    )
+}
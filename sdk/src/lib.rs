@@ -0,0 +1,12 @@
+// sdk/src/lib.rs
+//! Off-chain client for the Prism liquidity pool program: PDA derivation,
+//! instruction builders, account decoders and a quote engine. Everything is
+//! built on the program crate's own types and math, so layouts and quotes
+//! cannot drift from what the program executes.
+pub mod decode;
+pub mod instructions;
+pub mod pda;
+pub mod quote;
+
+pub use anchor_lang;
+pub use liquidity_pool::{self, instruction as args, state, utils::math::Price, ID};
@@ -0,0 +1,219 @@
+// sdk/src/pda.rs
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use liquidity_pool::state::{
+    AMM_LP_LOCK_SEED, AMM_LP_MINT_SEED, AMM_POOL_SEED, AMM_QUOTE_VAULT_SEED, AMM_SOL_VAULT_SEED, AMM_TOKEN_VAULT_SEED,
+    CREATOR_ALLOWLIST_SEED, FEE_ACCOUNT_SEED, FEE_VAULT_SEED, GLOBAL_CONFIG_SEED, LP_LOCK_SEED, LP_MINT_SEED,
+    MINT_REGISTRY_SEED, NATIVE_QUOTE_MINT, ORACLE_SEED, POOL_REGISTRY_SEED, POOL_SEED, POOL_TOKEN_SEED, PROTOCOL_FEE_ACCOUNT_SEED,
+    QUOTE_VAULT_SEED, SOL_VAULT_SEED, SWAP_WSOL_SEED,
+};
+use liquidity_pool::ID;
+
+pub fn find_pool(token_mint: &Pubkey, quote_mint: &Pubkey, pool_index: u8) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[POOL_SEED, token_mint.as_ref(), quote_mint.as_ref(), &[pool_index]], &ID)
+}
+
+pub fn find_pool_registry(token_mint: &Pubkey, quote_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[POOL_REGISTRY_SEED, token_mint.as_ref(), quote_mint.as_ref()], &ID)
+}
+
+/// Lists the quote mints `token_mint` has pools against. Each one leads to a
+/// `find_pool_registry` account.
+pub fn find_mint_registry(token_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MINT_REGISTRY_SEED, token_mint.as_ref()], &ID)
+}
+
+pub fn find_pool_token_account(pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[POOL_TOKEN_SEED, pool.as_ref()], &ID)
+}
+
+pub fn find_quote_vault(pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[QUOTE_VAULT_SEED, pool.as_ref()], &ID)
+}
+
+pub fn find_fee_vault(pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[FEE_VAULT_SEED, pool.as_ref()], &ID)
+}
+
+pub fn find_sol_vault(pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SOL_VAULT_SEED, pool.as_ref()], &ID)
+}
+
+pub fn find_oracle(pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ORACLE_SEED, pool.as_ref()], &ID)
+}
+
+pub fn find_lp_mint(pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[LP_MINT_SEED, pool.as_ref()], &ID)
+}
+
+pub fn find_lp_lock(pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[LP_LOCK_SEED, pool.as_ref()], &ID)
+}
+
+/// The ephemeral account a `swap_wsol` buy by `user` unwraps through.
+pub fn find_swap_wsol_account(pool: &Pubkey, user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SWAP_WSOL_SEED, pool.as_ref(), user.as_ref()], &ID)
+}
+
+pub fn find_fee_account(pool: &Pubkey, recipient: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[FEE_ACCOUNT_SEED, pool.as_ref(), recipient.as_ref()], &ID)
+}
+
+/// The pool's protocol fee account, claimable by whoever is the treasury.
+pub fn find_protocol_fee_account(pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[FEE_ACCOUNT_SEED, pool.as_ref(), PROTOCOL_FEE_ACCOUNT_SEED], &ID)
+}
+
+pub fn find_global_config() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[GLOBAL_CONFIG_SEED], &ID)
+}
+
+pub fn find_creator_allowlist_entry(creator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CREATOR_ALLOWLIST_SEED, creator.as_ref()], &ID)
+}
+
+pub fn find_amm_pool(token_mint: &Pubkey, quote_mint: &Pubkey, pool_index: u8) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[AMM_POOL_SEED, token_mint.as_ref(), quote_mint.as_ref(), &[pool_index]], &ID)
+}
+
+pub fn find_amm_quote_vault(amm_pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[AMM_QUOTE_VAULT_SEED, amm_pool.as_ref()], &ID)
+}
+
+pub fn find_amm_sol_vault(amm_pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[AMM_SOL_VAULT_SEED, amm_pool.as_ref()], &ID)
+}
+
+pub fn find_amm_token_vault(amm_pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[AMM_TOKEN_VAULT_SEED, amm_pool.as_ref()], &ID)
+}
+
+pub fn find_amm_lp_mint(amm_pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[AMM_LP_MINT_SEED, amm_pool.as_ref()], &ID)
+}
+
+pub fn find_amm_lp_lock(amm_pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[AMM_LP_LOCK_SEED, amm_pool.as_ref()], &ID)
+}
+
+pub fn find_event_authority() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"__event_authority"], &ID)
+}
+
+pub fn find_program_data() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ID.as_ref()], &bpf_loader_upgradeable::ID)
+}
+
+/// Every PDA belonging to the launch pool of `token_mint` against
+/// `quote_mint` at `pool_index`, along with the token programs that own both mints (legacy SPL
+/// Token or Token-2022).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PoolAddresses {
+    pub token_mint: Pubkey,
+    pub token_program: Pubkey,
+    pub quote_mint: Pubkey,
+    pub quote_token_program: Pubkey,
+    pub pool_index: u8,
+    pub mint_registry: Pubkey,
+    pub pool_registry: Pubkey,
+    pub pool: Pubkey,
+    pub pool_token_account: Pubkey,
+    pub fee_vault: Pubkey,
+    pub sol_vault: Pubkey,
+    pub quote_vault: Pubkey,
+    pub oracle: Pubkey,
+    pub lp_mint: Pubkey,
+    pub lp_lock_account: Pubkey,
+}
+
+impl PoolAddresses {
+    /// Addresses of the first SOL pool of `token_mint`.
+    pub fn new(token_mint: Pubkey, token_program: Pubkey) -> Self {
+        Self::with_quote(token_mint, token_program, NATIVE_QUOTE_MINT, anchor_spl::token::ID, 0)
+    }
+
+    pub fn with_quote(
+        token_mint: Pubkey,
+        token_program: Pubkey,
+        quote_mint: Pubkey,
+        quote_token_program: Pubkey,
+        pool_index: u8,
+    ) -> Self {
+        let (pool, _) = find_pool(&token_mint, &quote_mint, pool_index);
+
+        Self {
+            token_mint,
+            token_program,
+            quote_mint,
+            quote_token_program,
+            pool_index,
+            mint_registry: find_mint_registry(&token_mint).0,
+            pool_registry: find_pool_registry(&token_mint, &quote_mint).0,
+            pool,
+            pool_token_account: find_pool_token_account(&pool).0,
+            fee_vault: find_fee_vault(&pool).0,
+            sol_vault: find_sol_vault(&pool).0,
+            quote_vault: find_quote_vault(&pool).0,
+            oracle: find_oracle(&pool).0,
+            lp_mint: find_lp_mint(&pool).0,
+            lp_lock_account: find_lp_lock(&pool).0,
+        }
+    }
+
+    pub fn is_native_quote(&self) -> bool {
+        self.quote_mint == NATIVE_QUOTE_MINT
+    }
+}
+
+/// Every PDA belonging to the graduated AMM pool of `token_mint` against
+/// `quote_mint` at `pool_index`, along with the token programs that own both mints.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AmmPoolAddresses {
+    pub token_mint: Pubkey,
+    pub token_program: Pubkey,
+    pub quote_mint: Pubkey,
+    pub quote_token_program: Pubkey,
+    pub pool_index: u8,
+    pub amm_pool: Pubkey,
+    pub amm_token_vault: Pubkey,
+    pub amm_sol_vault: Pubkey,
+    pub amm_quote_vault: Pubkey,
+    pub lp_mint: Pubkey,
+    pub lp_lock_account: Pubkey,
+}
+
+impl AmmPoolAddresses {
+    /// Addresses of the first SOL AMM pool of `token_mint`.
+    pub fn new(token_mint: Pubkey, token_program: Pubkey) -> Self {
+        Self::with_quote(token_mint, token_program, NATIVE_QUOTE_MINT, anchor_spl::token::ID, 0)
+    }
+
+    pub fn with_quote(
+        token_mint: Pubkey,
+        token_program: Pubkey,
+        quote_mint: Pubkey,
+        quote_token_program: Pubkey,
+        pool_index: u8,
+    ) -> Self {
+        let (amm_pool, _) = find_amm_pool(&token_mint, &quote_mint, pool_index);
+
+        Self {
+            token_mint,
+            token_program,
+            quote_mint,
+            quote_token_program,
+            pool_index,
+            amm_pool,
+            amm_token_vault: find_amm_token_vault(&amm_pool).0,
+            amm_sol_vault: find_amm_sol_vault(&amm_pool).0,
+            amm_quote_vault: find_amm_quote_vault(&amm_pool).0,
+            lp_mint: find_amm_lp_mint(&amm_pool).0,
+            lp_lock_account: find_amm_lp_lock(&amm_pool).0,
+        }
+    }
+
+    pub fn is_native_quote(&self) -> bool {
+        self.quote_mint == NATIVE_QUOTE_MINT
+    }
+}
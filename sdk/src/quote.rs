@@ -0,0 +1,70 @@
+// sdk/src/quote.rs
+use anchor_lang::prelude::*;
+use liquidity_pool::state::{LiquidityPool, SwapDirection, SwapMode, SwapQuote};
+use liquidity_pool::utils::quote::{quote_swap, simulate_swap, CurveSwap};
+
+pub use liquidity_pool::utils::quote::apply_swap;
+pub use liquidity_pool::utils::token::{
+    calculate_gross_transfer_amount, calculate_transfer_fee, MintTransferFee, NoTransferFee, TransferFee,
+};
+
+/// Quotes a swap with the program's own pricing code, so the result is exactly
+/// what `swap` would execute against `pool` (and what `calculate_swap` returns).
+/// `transfer_fee` is the token mint's fee on a transfer, e.g.
+/// `&MintTransferFee { mint_data: &mint_data, epoch }`, or `&NoTransferFee`.
+pub fn quote(
+    pool: &LiquidityPool,
+    token_decimals: u8,
+    direction: SwapDirection,
+    mode: SwapMode,
+    transfer_fee: &impl TransferFee,
+) -> Result<SwapQuote> {
+    simulate_swap(pool, token_decimals, direction, mode, transfer_fee)
+}
+
+/// The full breakdown of a swap: curve and fee legs, and whether it completes
+/// the curve.
+pub fn quote_detailed(
+    pool: &LiquidityPool,
+    token_decimals: u8,
+    direction: SwapDirection,
+    mode: SwapMode,
+    transfer_fee: &impl TransferFee,
+) -> Result<CurveSwap> {
+    quote_swap(pool, token_decimals, direction, mode, transfer_fee)
+}
+
+pub fn quote_exact_in(
+    pool: &LiquidityPool,
+    token_decimals: u8,
+    direction: SwapDirection,
+    amount_in: u64,
+) -> Result<SwapQuote> {
+    quote(pool, token_decimals, direction, SwapMode::ExactIn { amount_in, min_amount_out: 0 }, &NoTransferFee)
+}
+
+pub fn quote_exact_out(
+    pool: &LiquidityPool,
+    token_decimals: u8,
+    direction: SwapDirection,
+    amount_out: u64,
+) -> Result<SwapQuote> {
+    quote(pool, token_decimals, direction, SwapMode::ExactOut { amount_out, max_amount_in: u64::MAX }, &NoTransferFee)
+}
+
+/// Quotes both legs of `swap_via_sol`: selling `amount_in` into
+/// `input_pool`, then spending the quote amount it returns on `output_pool`.
+/// Both pools must share a quote mint. Transfer fees of Token-2022 mints are
+/// not deducted.
+pub fn quote_via_sol(
+    input_pool: &LiquidityPool,
+    input_decimals: u8,
+    output_pool: &LiquidityPool,
+    output_decimals: u8,
+    amount_in: u64,
+) -> Result<(SwapQuote, SwapQuote)> {
+    let sell = quote_exact_in(input_pool, input_decimals, SwapDirection::Sell, amount_in)?;
+    let buy = quote_exact_in(output_pool, output_decimals, SwapDirection::Buy, sell.amount_out)?;
+
+    Ok((sell, buy))
+}
@@ -0,0 +1,437 @@
+// sdk/tests/quote.rs
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::AccountSerialize;
+use prism_liquidity_pool_sdk::decode::decode_pool;
+use prism_liquidity_pool_sdk::liquidity_pool::utils::curve::Curve;
+use prism_liquidity_pool_sdk::liquidity_pool::utils::quote::{quote_swap, simulate_swap, CurveSwap};
+use prism_liquidity_pool_sdk::quote::{
+    apply_swap, quote, quote_detailed, quote_exact_in, quote_exact_out, quote_via_sol, NoTransferFee, TransferFee,
+};
+use prism_liquidity_pool_sdk::state::{
+    CompletionThreshold, CurveKind, LiquidityPool, PoolStatus, SwapDirection, SwapMode, NATIVE_QUOTE_MINT,
+};
+use prism_liquidity_pool_sdk::Price;
+
+const SOL: u64 = 1_000_000_000;
+const TOKEN: u64 = 1_000_000_000;
+const DECIMALS: [u8; 2] = [6, 9];
+const DIRECTIONS: [SwapDirection; 2] = [SwapDirection::Buy, SwapDirection::Sell];
+
+fn pool(curve: CurveKind, tokens_sold: u64, real_sol: u64, token_decimals: u8) -> LiquidityPool {
+    let (virtual_sol, virtual_token) = match curve {
+        CurveKind::ConstantProduct => (30 * SOL, 1_073_000_000 * TOKEN),
+        _ => (0, 0),
+    };
+
+    LiquidityPool {
+        authority: Pubkey::new_unique(),
+        token_mint: Pubkey::new_unique(),
+        quote_mint: NATIVE_QUOTE_MINT,
+        pool_index: 0,
+        exchange_rate: 27_958,
+        virtual_sol,
+        virtual_token,
+        real_sol,
+        real_token: 800_000_000 * 10u64.pow(token_decimals as u32),
+        is_active: true,
+        created_at: 1_700_000_000,
+        fee_basis_points: 100,
+        bump: 255,
+        total_volume_sol: 0,
+        total_volume_token: 0,
+        trade_count: 0,
+        last_trade_timestamp: 0,
+        current_price: Price::ZERO,
+        curve,
+        tokens_sold,
+        status: PoolStatus::Trading,
+        completion_threshold: CompletionThreshold::None,
+        sol_vault_bump: 255,
+        price_cumulative: 0,
+        price_last_updated: 1_700_000_000,
+    }
+}
+
+/// Every curve kind, fresh and mid-curve, with and without a completion
+/// threshold close enough for the larger buys to hit it.
+fn pool_states(token_decimals: u8) -> Vec<LiquidityPool> {
+    let curves = [
+        CurveKind::ConstantProduct,
+        CurveKind::Linear { base_price: 28_000_000_000_000, slope: 465_000 },
+        CurveKind::Exponential { base_price: 28_000_000_000_000, growth_rate: 4_000_000_000 },
+    ];
+
+    let mut states = Vec::new();
+    for curve in curves {
+        for (tokens_sold, real_sol) in [(0, 0), (100_000_000 * TOKEN, 5 * SOL)] {
+            for completion_threshold in [
+                CompletionThreshold::None,
+                CompletionThreshold::SolRaised(real_sol + 2 * SOL),
+                CompletionThreshold::TokensSold(tokens_sold + 5_000_000 * TOKEN),
+            ] {
+                let mut pool = pool(curve, tokens_sold, real_sol, token_decimals);
+                pool.completion_threshold = completion_threshold;
+                states.push(pool);
+            }
+        }
+    }
+
+    states
+}
+
+fn modes(direction: SwapDirection, token_decimals: u8) -> Vec<SwapMode> {
+    let token = 10u64.pow(token_decimals as u32);
+    let sol_amounts = [1, 1_000_000, SOL, 10 * SOL];
+    let token_amounts = [1, token, 1_000_000 * token, 50_000_000 * token];
+    let (amounts_in, amounts_out) = match direction {
+        SwapDirection::Buy => (sol_amounts, token_amounts),
+        SwapDirection::Sell => (token_amounts, sol_amounts),
+    };
+
+    let mut modes = Vec::new();
+    for amount_in in amounts_in {
+        modes.push(SwapMode::ExactIn { amount_in, min_amount_out: 0 });
+        modes.push(SwapMode::ExactIn { amount_in, min_amount_out: u64::MAX });
+    }
+    for amount_out in amounts_out {
+        modes.push(SwapMode::ExactOut { amount_out, max_amount_in: u64::MAX });
+        modes.push(SwapMode::ExactOut { amount_out, max_amount_in: 1 });
+    }
+
+    modes
+}
+
+/// A 1% transfer fee, rounded down.
+struct OnePercentFee;
+
+impl TransferFee for OnePercentFee {
+    fn fee(&self, amount: u64) -> anchor_lang::Result<u64> {
+        Ok(amount / 100)
+    }
+
+    fn gross_amount(&self, net_amount: u64) -> anchor_lang::Result<u64> {
+        let mut gross_amount = net_amount * 100 / 99;
+        while gross_amount - self.fee(gross_amount)? < net_amount {
+            gross_amount += 1;
+        }
+        Ok(gross_amount)
+    }
+}
+
+/// The pool as a client sees it: serialized by the program and decoded by the
+/// sdk.
+fn fetched(pool: &LiquidityPool) -> LiquidityPool {
+    let mut data = Vec::new();
+    pool.try_serialize(&mut data).unwrap();
+    decode_pool(&data).unwrap()
+}
+
+/// A constant-product pool over 100 SOL of virtual reserve and 1,000,000
+/// six-decimal tokens, after `tokens_sold` tokens went out for `real_sol`.
+/// Round numbers, so the expected quotes below can be worked out by hand.
+fn round_pool(tokens_sold: u64, real_sol: u64) -> LiquidityPool {
+    let mut pool = pool(CurveKind::ConstantProduct, tokens_sold * TOKEN, real_sol, 6);
+    pool.virtual_sol = 100 * SOL;
+    pool.virtual_token = 0;
+    pool.real_token = (1_000_000 - tokens_sold) * 1_000_000;
+    pool
+}
+
+fn detailed(pool: &LiquidityPool, direction: SwapDirection, mode: SwapMode) -> CurveSwap {
+    quote_detailed(&fetched(pool), 6, direction, mode, &NoTransferFee).unwrap()
+}
+
+#[test]
+fn constant_product_quotes_match_known_answers() {
+    // Reserves 100e9 lamports / 1e15 normalized units: 0.1 SOL per token.
+    let fresh = round_pool(0, 0);
+    let spot_price = 100_000_000_000_000_000;
+
+    // 1 SOL in, 1% fee: 1e15 * 0.99e9 / 100.99e9 = 9_802_950_787_206 units,
+    // truncated to 9_802.950787 tokens.
+    let buy = SwapMode::ExactIn { amount_in: SOL, min_amount_out: 0 };
+    assert_eq!(
+        detailed(&fresh, SwapDirection::Buy, buy),
+        CurveSwap {
+            token_amount: 9_802_950_787,
+            user_token_amount: 9_802_950_787,
+            normalized_token_amount: 9_802_950_787_000,
+            sol_amount: SOL,
+            curve_sol_amount: 990_000_000,
+            fee_amount: 10_000_000,
+            completes_curve: false,
+            spot_price,
+            price_impact_bps: 99,
+        }
+    );
+
+    // 1,000 tokens out: ceil(100e9 * 1e12 / 999e12) = 100_100_101 to the
+    // curve, grossed up by 1% to ceil(100_100_101 / 0.99).
+    let buy = SwapMode::ExactOut { amount_out: 1_000_000_000, max_amount_in: u64::MAX };
+    assert_eq!(
+        detailed(&fresh, SwapDirection::Buy, buy),
+        CurveSwap {
+            token_amount: 1_000_000_000,
+            user_token_amount: 1_000_000_000,
+            normalized_token_amount: 1_000 * TOKEN,
+            sol_amount: 101_111_214,
+            curve_sol_amount: 100_100_101,
+            fee_amount: 1_011_113,
+            completes_curve: false,
+            spot_price,
+            price_impact_bps: 10,
+        }
+    );
+
+    // 90,000 tokens sold for 10 SOL: reserves 110e9 / 910e12.
+    let traded = round_pool(90_000, 10 * SOL);
+    let spot_price = 120_879_120_879_120_879;
+
+    // 5,000 tokens in: 110e9 * 5e12 / 915e12 = 601_092_896, less 1%.
+    let sell = SwapMode::ExactIn { amount_in: 5_000_000_000, min_amount_out: 0 };
+    assert_eq!(
+        detailed(&traded, SwapDirection::Sell, sell),
+        CurveSwap {
+            token_amount: 5_000_000_000,
+            user_token_amount: 5_000_000_000,
+            normalized_token_amount: 5_000 * TOKEN,
+            sol_amount: 595_081_968,
+            curve_sol_amount: 601_092_896,
+            fee_amount: 6_010_928,
+            completes_curve: false,
+            spot_price,
+            price_impact_bps: 54,
+        }
+    );
+
+    // 0.5 SOL out takes ceil(0.5e9 / 0.99) = 505_050_506 from the curve, for
+    // ceil(910e12 * 505_050_506 / (110e9 - 505_050_506)) units, rounded up to
+    // whole raw tokens.
+    let sell = SwapMode::ExactOut { amount_out: SOL / 2, max_amount_in: u64::MAX };
+    assert_eq!(
+        detailed(&traded, SwapDirection::Sell, sell),
+        CurveSwap {
+            token_amount: 4_197_416_983,
+            user_token_amount: 4_197_416_983,
+            normalized_token_amount: 4_197_416_983_000,
+            sol_amount: SOL / 2,
+            curve_sol_amount: 505_050_506,
+            fee_amount: 5_050_506,
+            completes_curve: false,
+            spot_price,
+            price_impact_bps: 45,
+        }
+    );
+}
+
+#[test]
+fn completing_buy_is_capped_at_the_threshold() {
+    let mut pool = round_pool(0, 0);
+    pool.completion_threshold = CompletionThreshold::TokensSold(5_000 * TOKEN);
+
+    // 1 SOL would buy 9,802 tokens; only 5,000 are left, for
+    // ceil(100e9 * 5e12 / 995e12) = 502_512_563 plus the fee on top.
+    let buy = SwapMode::ExactIn { amount_in: SOL, min_amount_out: 0 };
+    assert_eq!(
+        detailed(&pool, SwapDirection::Buy, buy),
+        CurveSwap {
+            token_amount: 5_000_000_000,
+            user_token_amount: 5_000_000_000,
+            normalized_token_amount: 5_000 * TOKEN,
+            sol_amount: 507_588_448,
+            curve_sol_amount: 502_512_563,
+            fee_amount: 5_075_885,
+            completes_curve: true,
+            spot_price: 100_000_000_000_000_000,
+            price_impact_bps: 50,
+        }
+    );
+}
+
+#[test]
+fn linear_buy_matches_known_answer() {
+    // Buying up to supply s costs F(s) = b * s + slope * s^2 / 2 lamports,
+    // scaled by 1e30, with b = base_price * 1e9.
+    let mut pool = pool(CurveKind::Linear { base_price: 1_000_000_000_000_000, slope: 1_000_000 }, 0, 0, 6);
+    pool.fee_basis_points = 100;
+
+    // s solves F(s) = 0.99e9 * 1e30:
+    // (sqrt(b^2 + 2 * slope * cost) - b) / slope = 989_510_434_549_958 units.
+    let buy = SwapMode::ExactIn { amount_in: SOL, min_amount_out: 0 };
+    assert_eq!(
+        detailed(&pool, SwapDirection::Buy, buy),
+        CurveSwap {
+            token_amount: 989_510_434_549,
+            user_token_amount: 989_510_434_549,
+            normalized_token_amount: 989_510_434_549_000,
+            sol_amount: SOL,
+            curve_sol_amount: 990_000_000,
+            fee_amount: 10_000_000,
+            completes_curve: false,
+            spot_price: 1_000_000_000_000_000,
+            price_impact_bps: 4,
+        }
+    );
+    assert_eq!(
+        quote(&fetched(&pool), 6, SwapDirection::Buy, buy, &NoTransferFee).unwrap().new_spot_price,
+        Price::new(1_000_989_510_434_549)
+    );
+}
+
+#[test]
+fn applied_swaps_move_the_reserves_by_the_quote() {
+    let start = round_pool(0, 0);
+    let mut pool = start.clone();
+
+    let buy = SwapMode::ExactIn { amount_in: SOL, min_amount_out: 0 };
+    let bought = detailed(&pool, SwapDirection::Buy, buy);
+    apply_swap(&mut pool, SwapDirection::Buy, &bought).unwrap();
+    assert_eq!(pool.real_sol, 990_000_000);
+    assert_eq!(pool.real_token, start.real_token - 9_802_950_787);
+    assert_eq!(pool.tokens_sold, 9_802_950_787_000);
+
+    // The quote's new spot price is the one the applied pool prices at:
+    // 100.99e9 * 1e21 / (1e15 - 9_802_950_787_000).
+    let spot_price = 101_989_800_999_978_714;
+    assert_eq!(pool.pricing_curve(6).unwrap().spot_price().unwrap(), spot_price);
+    assert_eq!(
+        quote(&fetched(&start), 6, SwapDirection::Buy, buy, &NoTransferFee).unwrap().new_spot_price,
+        Price::new(spot_price)
+    );
+
+    // Selling them back takes 100.99e9 * 9_802_950_787e3 / 1e15 = 989_999_999
+    // from the curve, 980_100_000 after the fee.
+    let sell = SwapMode::ExactIn { amount_in: bought.user_token_amount, min_amount_out: 0 };
+    let sold = detailed(&pool, SwapDirection::Sell, sell);
+    assert_eq!((sold.sol_amount, sold.curve_sol_amount, sold.fee_amount), (980_100_000, 989_999_999, 9_899_999));
+    apply_swap(&mut pool, SwapDirection::Sell, &sold).unwrap();
+
+    // The round trip leaves the curve where it started, one lamport of
+    // rounding richer.
+    assert_eq!(pool.real_token, start.real_token);
+    assert_eq!(pool.tokens_sold, 0);
+    assert_eq!(pool.real_sol, 1);
+}
+
+#[test]
+fn buy_slippage_is_checked_net_of_the_transfer_fee() {
+    for pool in pool_states(6) {
+        let amount_in = SOL;
+        let unbounded = SwapMode::ExactIn { amount_in, min_amount_out: 0 };
+        let swap = quote_swap(&pool, 6, SwapDirection::Buy, unbounded, &OnePercentFee).unwrap();
+        let received = swap.token_amount - swap.token_amount / 100;
+        assert_eq!(swap.user_token_amount, received);
+
+        let at_net = SwapMode::ExactIn { amount_in, min_amount_out: received };
+        assert_eq!(quote_swap(&pool, 6, SwapDirection::Buy, at_net, &OnePercentFee), Ok(swap));
+
+        let above_net = SwapMode::ExactIn { amount_in, min_amount_out: received + 1 };
+        assert!(quote_swap(&pool, 6, SwapDirection::Buy, above_net, &OnePercentFee).is_err());
+        assert!(quote_swap(&pool, 6, SwapDirection::Buy, above_net, &NoTransferFee).is_ok());
+    }
+}
+
+#[test]
+fn exact_out_buys_gross_the_vault_output_up_for_the_transfer_fee() {
+    for pool in pool_states(6) {
+        let amount_out = 1_000_000;
+        let exact_out = SwapMode::ExactOut { amount_out, max_amount_in: u64::MAX };
+        let swap = quote_swap(&pool, 6, SwapDirection::Buy, exact_out, &OnePercentFee).unwrap();
+
+        // Completing buys are capped at the remaining supply instead.
+        if !swap.completes_curve {
+            assert_eq!(swap.token_amount, OnePercentFee.gross_amount(amount_out).unwrap());
+            assert!(swap.user_token_amount >= amount_out);
+        }
+        assert_eq!(swap.user_token_amount, swap.token_amount - swap.token_amount / 100);
+    }
+}
+
+#[test]
+fn sell_input_is_quoted_net_of_the_transfer_fee() {
+    for pool in pool_states(6).into_iter().filter(|pool| pool.tokens_sold > 0) {
+        // The curve only buys back what reaches the vault.
+        let amount_in = 1_000_000_000_000;
+        let exact_in = SwapMode::ExactIn { amount_in, min_amount_out: 0 };
+        let swap = quote_swap(&pool, 6, SwapDirection::Sell, exact_in, &OnePercentFee).unwrap();
+
+        let received = amount_in - amount_in / 100;
+        let without_fee = SwapMode::ExactIn { amount_in: received, min_amount_out: 0 };
+        let expected = quote_swap(&pool, 6, SwapDirection::Sell, without_fee, &NoTransferFee).unwrap();
+        assert_eq!(swap, CurveSwap { user_token_amount: amount_in, ..expected });
+
+        let above_output = SwapMode::ExactIn { amount_in, min_amount_out: swap.sol_amount + 1 };
+        assert!(quote_swap(&pool, 6, SwapDirection::Sell, above_output, &OnePercentFee).is_err());
+
+        // An exact-out sell bounds the gross amount the user sends.
+        let exact_out = SwapMode::ExactOut { amount_out: swap.sol_amount, max_amount_in: u64::MAX };
+        let swap = quote_swap(&pool, 6, SwapDirection::Sell, exact_out, &OnePercentFee).unwrap();
+        assert_eq!(swap.user_token_amount, OnePercentFee.gross_amount(swap.token_amount).unwrap());
+        assert!(swap.user_token_amount - swap.user_token_amount / 100 >= swap.token_amount);
+
+        let at_gross = SwapMode::ExactOut { amount_out: swap.sol_amount, max_amount_in: swap.user_token_amount };
+        assert_eq!(quote_swap(&pool, 6, SwapDirection::Sell, at_gross, &OnePercentFee), Ok(swap));
+
+        let below_gross = SwapMode::ExactOut { amount_out: swap.sol_amount, max_amount_in: swap.user_token_amount - 1 };
+        assert!(quote_swap(&pool, 6, SwapDirection::Sell, below_gross, &OnePercentFee).is_err());
+        assert!(quote_swap(&pool, 6, SwapDirection::Sell, below_gross, &NoTransferFee).is_ok());
+    }
+}
+
+#[test]
+fn exact_quote_helpers_match_unbounded_modes() {
+    for token_decimals in DECIMALS {
+        for pool in pool_states(token_decimals) {
+            for direction in DIRECTIONS {
+                for mode in modes(direction, token_decimals) {
+                    match mode {
+                        SwapMode::ExactIn { amount_in, min_amount_out: 0 } => assert_eq!(
+                            quote_exact_in(&pool, token_decimals, direction, amount_in),
+                            simulate_swap(&pool, token_decimals, direction, mode, &NoTransferFee),
+                        ),
+                        SwapMode::ExactOut { amount_out, max_amount_in: u64::MAX } => assert_eq!(
+                            quote_exact_out(&pool, token_decimals, direction, amount_out),
+                            simulate_swap(&pool, token_decimals, direction, mode, &NoTransferFee),
+                        ),
+                        _ => {},
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn quote_via_sol_chains_both_program_quotes() {
+    for input_decimals in DECIMALS {
+        for output_decimals in DECIMALS {
+            for input_pool in pool_states(input_decimals).iter().filter(|pool| pool.tokens_sold > 0) {
+                for output_pool in pool_states(output_decimals) {
+                    let amount_in = 1_000_000 * 10u64.pow(input_decimals as u32);
+                    let (sell, buy) =
+                        quote_via_sol(input_pool, input_decimals, &output_pool, output_decimals, amount_in).unwrap();
+
+                    let expected_sell = simulate_swap(
+                        input_pool,
+                        input_decimals,
+                        SwapDirection::Sell,
+                        SwapMode::ExactIn { amount_in, min_amount_out: 0 },
+                        &NoTransferFee,
+                    )
+                    .unwrap();
+                    let expected_buy = simulate_swap(
+                        &output_pool,
+                        output_decimals,
+                        SwapDirection::Buy,
+                        SwapMode::ExactIn { amount_in: expected_sell.amount_out, min_amount_out: 0 },
+                        &NoTransferFee,
+                    )
+                    .unwrap();
+
+                    assert_eq!(sell, expected_sell);
+                    assert_eq!(buy, expected_buy);
+                }
+            }
+        }
+    }
+}